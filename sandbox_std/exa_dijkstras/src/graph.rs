@@ -0,0 +1,354 @@
+//! Граф общего вида: данные вершин `N`, веса рёбер `E`, ориентированный или нет.
+//!
+//! Вершины и рёбра хранятся в списках смежности и получают идентификаторы
+//! [`NodeId`] и [`EdgeId`]. Идентификаторы не меняются при удалении других вершин и
+//! рёбер; идентификатор удалённого элемента больше не выдаётся.
+
+/// Идентификатор вершины.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// Идентификатор вершины, добавленной `index`-й по счёту (с нуля).
+    /// Для графа из [`crate::CityMap`] совпадает с индексом города.
+    pub fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    /// Порядковый номер вершины.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Идентификатор ребра.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EdgeId(usize);
+
+impl EdgeId {
+    /// Порядковый номер ребра.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Вес ребра: упорядочен и складывается. Реализован только для беззнаковых целых,
+/// поэтому отрицательный вес, на котором алгоритм Дейкстры ошибается, не записать.
+/// Собственные реализации должны соблюдать то же: любой вес не меньше [`Weight::ZERO`].
+pub trait Weight: Ord + Copy {
+    /// Нулевой вес — длина пути из вершины в неё саму.
+    const ZERO: Self;
+
+    /// Сумма весов или `None` при переполнении.
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_weight {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                const ZERO: Self = 0;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_weight!(u8, u16, u32, u64, u128, usize);
+
+#[derive(Debug, Clone)]
+struct Node<N> {
+    data: N,
+    /// Исходящие рёбра; для неориентированного графа — все инцидентные.
+    outgoing: Vec<EdgeId>,
+    /// Входящие рёбра ориентированного графа.
+    incoming: Vec<EdgeId>,
+}
+
+#[derive(Debug, Clone)]
+struct Edge<E> {
+    from: NodeId,
+    to: NodeId,
+    weight: E,
+}
+
+/// Граф со списками смежности.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_dijkstras::Graph;
+///
+/// let mut roads = Graph::undirected();
+/// let barnaul = roads.add_node("Барнаул");
+/// let biysk = roads.add_node("Бийск");
+/// let tomsk = roads.add_node("Томск");
+/// roads.add_edge(barnaul, biysk, 159);
+/// let edge = roads.add_edge(barnaul, tomsk, 493);
+///
+/// // В неориентированном графе ребро видно из обеих вершин.
+/// let from_tomsk: Vec<_> = roads.neighbors(tomsk).map(|(city, &km)| (roads[city], km)).collect();
+/// assert_eq!(from_tomsk, [("Барнаул", 493)]);
+///
+/// assert_eq!(roads.remove_edge(edge), Some(493));
+/// assert_eq!(roads.neighbors(tomsk).count(), 0);
+/// assert_eq!(roads.remove_node(biysk), Some("Бийск"));
+/// assert_eq!((roads.node_count(), roads.edge_count()), (2, 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Graph<N, E> {
+    directed: bool,
+    /// Вершины по идентификаторам; `None` — удалённая вершина.
+    nodes: Vec<Option<Node<N>>>,
+    /// Рёбра по идентификаторам; `None` — удалённое ребро.
+    edges: Vec<Option<Edge<E>>>,
+    node_count: usize,
+    edge_count: usize,
+}
+
+impl<N, E> Graph<N, E> {
+    /// Пустой граф; `directed` — ориентированный ли он.
+    pub fn new(directed: bool) -> Self {
+        Self {
+            directed,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            node_count: 0,
+            edge_count: 0,
+        }
+    }
+
+    /// Пустой ориентированный граф.
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    /// Пустой неориентированный граф.
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    /// Ориентирован ли граф.
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Количество вершин.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Количество рёбер.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Верхняя граница порядковых номеров вершин: все [`NodeId::index`] меньше неё.
+    pub(crate) fn node_bound(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Добавляет вершину.
+    pub fn add_node(&mut self, data: N) -> NodeId {
+        self.nodes.push(Some(Node {
+            data,
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+        }));
+        self.node_count += 1;
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Удаляет вершину вместе со всеми её рёбрами. Возвращает данные вершины или `None`,
+    /// если её нет.
+    pub fn remove_node(&mut self, id: NodeId) -> Option<N> {
+        let node = self.nodes.get_mut(id.0)?.take()?;
+        self.node_count -= 1;
+        for edge in node.outgoing.into_iter().chain(node.incoming) {
+            self.remove_edge(edge);
+        }
+        Some(node.data)
+    }
+
+    /// Данные вершины.
+    pub fn node(&self, id: NodeId) -> Option<&N> {
+        self.slot(id).map(|node| &node.data)
+    }
+
+    /// Изменяемые данные вершины.
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut N> {
+        self.nodes
+            .get_mut(id.0)?
+            .as_mut()
+            .map(|node| &mut node.data)
+    }
+
+    /// Вершины с их идентификаторами в порядке добавления.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| node.as_ref().map(|node| (NodeId(index), &node.data)))
+    }
+
+    /// Добавляет ребро `from → to` (для неориентированного графа — `from — to`).
+    ///
+    /// # Паника
+    ///
+    /// Если одной из вершин нет в графе.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: E) -> EdgeId {
+        assert!(
+            self.contains_node(from) && self.contains_node(to),
+            "вершины {from:?} и {to:?} должны быть в графе"
+        );
+        let id = EdgeId(self.edges.len());
+        self.edges.push(Some(Edge { from, to, weight }));
+        self.edge_count += 1;
+
+        self.slot_mut(from).outgoing.push(id);
+        if self.directed {
+            self.slot_mut(to).incoming.push(id);
+        } else if from != to {
+            self.slot_mut(to).outgoing.push(id);
+        }
+        id
+    }
+
+    /// Удаляет ребро. Возвращает его вес или `None`, если ребра нет.
+    pub fn remove_edge(&mut self, id: EdgeId) -> Option<E> {
+        let edge = self.edges.get_mut(id.0)?.take()?;
+        self.edge_count -= 1;
+
+        let directed = self.directed;
+        let mut detach = |node: NodeId, incoming: bool| {
+            if let Some(Some(node)) = self.nodes.get_mut(node.0) {
+                let list = if incoming {
+                    &mut node.incoming
+                } else {
+                    &mut node.outgoing
+                };
+                list.retain(|&edge| edge != id);
+            }
+        };
+        detach(edge.from, false);
+        detach(edge.to, directed);
+        Some(edge.weight)
+    }
+
+    /// Концы и вес ребра.
+    pub fn edge(&self, id: EdgeId) -> Option<(NodeId, NodeId, &E)> {
+        let edge = self.edges.get(id.0)?.as_ref()?;
+        Some((edge.from, edge.to, &edge.weight))
+    }
+
+    /// Рёбра с их идентификаторами в порядке добавления.
+    pub fn edges(&self) -> impl Iterator<Item = (EdgeId, NodeId, NodeId, &E)> {
+        self.edges.iter().enumerate().filter_map(|(index, edge)| {
+            let edge = edge.as_ref()?;
+            Some((EdgeId(index), edge.from, edge.to, &edge.weight))
+        })
+    }
+
+    /// Соседи вершины и веса рёбер к ним: концы исходящих рёбер, а для
+    /// неориентированного графа — вторые концы всех инцидентных рёбер.
+    /// Для отсутствующей вершины итератор пуст.
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item = (NodeId, &E)> {
+        let outgoing = self.slot(id).map_or(&[][..], |node| &node.outgoing);
+        outgoing.iter().map(move |edge| {
+            let edge = self.edges[edge.0].as_ref().expect("ребро вершины удалено");
+            let neighbor = if edge.from == id { edge.to } else { edge.from };
+            (neighbor, &edge.weight)
+        })
+    }
+
+    /// Есть ли вершина в графе.
+    pub fn contains_node(&self, id: NodeId) -> bool {
+        self.slot(id).is_some()
+    }
+
+    fn slot(&self, id: NodeId) -> Option<&Node<N>> {
+        self.nodes.get(id.0)?.as_ref()
+    }
+
+    fn slot_mut(&mut self, id: NodeId) -> &mut Node<N> {
+        self.nodes[id.0].as_mut().expect("вершина есть в графе")
+    }
+}
+
+impl<N, E> Default for Graph<N, E> {
+    /// Пустой ориентированный граф.
+    fn default() -> Self {
+        Self::directed()
+    }
+}
+
+impl<N, E> std::ops::Index<NodeId> for Graph<N, E> {
+    type Output = N;
+
+    fn index(&self, id: NodeId) -> &N {
+        self.node(id).expect("вершина есть в графе")
+    }
+}
+
+impl<N, E> std::ops::IndexMut<NodeId> for Graph<N, E> {
+    fn index_mut(&mut self, id: NodeId) -> &mut N {
+        self.node_mut(id).expect("вершина есть в графе")
+    }
+}
+
+#[test]
+fn test_directed() {
+    let mut graph: Graph<char, u32> = Graph::directed();
+    let [a, b, c] = ['a', 'b', 'c'].map(|name| graph.add_node(name));
+    let ab = graph.add_edge(a, b, 1);
+    graph.add_edge(b, c, 2);
+    graph.add_edge(c, a, 3);
+    graph.add_edge(a, a, 4);
+
+    let from_a: Vec<(NodeId, u32)> = graph.neighbors(a).map(|(n, &w)| (n, w)).collect();
+    assert_eq!(from_a, [(b, 1), (a, 4)]);
+    assert_eq!(graph.neighbors(b).map(|(n, _)| n).collect::<Vec<_>>(), [c]);
+
+    // Удаление вершины удаляет и входящие, и исходящие рёбра.
+    assert_eq!(graph.remove_node(c), Some('c'));
+    assert_eq!(graph.remove_node(c), None);
+    assert_eq!(graph.neighbors(b).count(), 0);
+    assert_eq!(graph.edge_count(), 2);
+    assert_eq!(graph.edge(ab), Some((a, b, &1)));
+    assert!(!graph.contains_node(c));
+
+    // Идентификаторы не переиспользуются.
+    let d = graph.add_node('d');
+    assert_eq!(d.index(), 3);
+    assert_eq!(
+        graph.nodes().map(|(_, &name)| name).collect::<String>(),
+        "abd"
+    );
+}
+
+#[test]
+fn test_undirected() {
+    let mut graph: Graph<(), u8> = Graph::undirected();
+    let [a, b, c] = [(); 3].map(|()| graph.add_node(()));
+    graph.add_edge(a, b, 1);
+    let loop_edge = graph.add_edge(c, c, 2);
+    graph.add_edge(b, c, 3);
+
+    assert_eq!(
+        graph.neighbors(b).map(|(n, _)| n).collect::<Vec<_>>(),
+        [a, c]
+    );
+    assert_eq!(
+        graph.neighbors(c).map(|(n, _)| n).collect::<Vec<_>>(),
+        [c, b]
+    );
+    assert_eq!(graph.remove_edge(loop_edge), Some(2));
+    assert_eq!(graph.remove_edge(loop_edge), None);
+
+    graph.remove_node(a);
+    assert_eq!(graph.neighbors(b).map(|(n, _)| n).collect::<Vec<_>>(), [c]);
+    assert_eq!(graph.edges().count(), 1);
+}
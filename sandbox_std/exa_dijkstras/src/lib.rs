@@ -19,72 +19,116 @@
 //!
 //! Сложность: O(V²) для простой реализации, O((V+E)logV) с использованием кучи (где V — количество вершин, E — количество рёбер).
 
+pub mod graph;
+
 use std::{cmp::Reverse, collections::BinaryHeap};
 
+pub use graph::{EdgeId, Graph, NodeId, Weight};
+
 /// Формат - Vec<(Название города, Vec<(Индекс вершины соседа, расстояние)>)>
-type CityMap = Vec<(String, Vec<(usize, usize)>)>;
+pub type CityMap = Vec<(String, Vec<(usize, usize)>)>;
+
+impl From<&CityMap> for Graph<String, usize> {
+    /// Ориентированный граф городов: у каждого города — рёбра из его списка соседей.
+    /// Город с индексом `i` получает идентификатор `NodeId::new(i)`.
+    fn from(cities: &CityMap) -> Self {
+        let mut graph = Graph::directed();
+        for (name, _) in cities {
+            graph.add_node(name.clone());
+        }
+        for (index, (_, roads)) in cities.iter().enumerate() {
+            for &(neighbor, distance) in roads {
+                graph.add_edge(NodeId::new(index), NodeId::new(neighbor), distance);
+            }
+        }
+        graph
+    }
+}
 
 /// Функция реализует алгоритм Дейкстры для нахождения кратчайшего пути
-/// от начального города (указывается индексом) до конечного города (указывается индексом).
-/// Возвращает вектор индексов городов, составляющих кратчайший путь.
-pub fn dijkstra(cities: &CityMap, start_idx: usize, end: usize) -> Vec<usize> {
-    let num_cities = cities.len();
-    let mut dist: Vec<usize> = vec![usize::MAX; num_cities]; // для хранения кратчайших расстояний. Индекс города - индекс в векторе расстояния
-    let mut prev: Vec<Option<usize>> = vec![None; num_cities]; // для восстановления пути
-    let mut visited = vec![false; num_cities]; // для отслеживания посещенных городов (вершин)
+/// от начальной вершины `start` до конечной вершины `end`.
+/// Возвращает вершины, составляющие кратчайший путь, или пустой вектор, если пути нет.
+/// Веса рёбер — любой тип, реализующий [`Weight`]; пути, длина которых переполняет тип
+/// веса, не рассматриваются.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_dijkstras::{Graph, dijkstra};
+///
+/// let mut graph = Graph::undirected();
+/// let [a, b, c, d] = ["a", "b", "c", "d"].map(|name| graph.add_node(name));
+/// graph.add_edge(a, b, 7_u64);
+/// graph.add_edge(a, c, 2);
+/// graph.add_edge(c, b, 3);
+/// assert_eq!(dijkstra(&graph, a, b), [a, c, b]);
+/// assert_eq!(dijkstra(&graph, a, d), []);
+/// ```
+///
+/// # Паника
+///
+/// Если `start` или `end` нет в графе.
+pub fn dijkstra<N, E: Weight>(graph: &Graph<N, E>, start: NodeId, end: NodeId) -> Vec<NodeId> {
+    assert!(
+        graph.contains_node(start) && graph.contains_node(end),
+        "вершины {start:?} и {end:?} должны быть в графе"
+    );
+    let bound = graph.node_bound();
+    let mut dist: Vec<Option<E>> = vec![None; bound]; // для хранения кратчайших расстояний. Индекс вершины - индекс в векторе расстояния, None - ∞
+    let mut prev: Vec<Option<NodeId>> = vec![None; bound]; // для восстановления пути
+    let mut visited = vec![false; bound]; // для отслеживания посещенных вершин
     let mut heap = BinaryHeap::new(); // для реализации очереди с приоритетом
 
     // Инициализация: расстояние от начальной вершины до самой себя = 0.
-    dist[start_idx] = 0;
-    heap.push(Reverse((0, start_idx)));
+    dist[start.index()] = Some(E::ZERO);
+    heap.push(Reverse((E::ZERO, start)));
 
-    while let Some(Reverse((heap_weight, heap_city))) = heap.pop() {
+    while let Some(Reverse((heap_weight, heap_node))) = heap.pop() {
         // Пропускаем уже обработанные вершины
-        if visited[heap_city] {
+        if visited[heap_node.index()] {
             continue;
         }
-        visited[heap_city] = true; // помечаем город как посещённый
+        visited[heap_node.index()] = true; // помечаем вершину как посещённую
 
         // Если достигли конечной точки — можно завершать
-        if heap_city == end {
+        if heap_node == end {
             break;
         }
 
         // Обход соседей текущей вершины
-        for &(city_index, weight) in &cities[heap_city].1 {
-            let new_dist = heap_weight + weight;
-            println!(
-                "{}->{}: {new_dist}",
-                cities[heap_city].0, cities[city_index].0
-            );
-            // Обновляем расстояние, если найден более короткий путь до этого города
-            if new_dist < dist[city_index] {
-                dist[city_index] = new_dist; // Обновляем расстояние от стартовой города до промежуточного, если нашли более короткий путь
-                prev[city_index] = Some(heap_city); // Фиксируем, из какого города пришли
-                heap.push(Reverse((new_dist, city_index)));
+        for (neighbor, &weight) in graph.neighbors(heap_node) {
+            let Some(new_dist) = heap_weight.checked_add(weight) else {
+                continue;
+            };
+            // Обновляем расстояние, если найден более короткий путь до этой вершины
+            if dist[neighbor.index()].is_none_or(|known| new_dist < known) {
+                dist[neighbor.index()] = Some(new_dist); // Обновляем расстояние от стартовой вершины до промежуточной, если нашли более короткий путь
+                prev[neighbor.index()] = Some(heap_node); // Фиксируем, из какой вершины пришли
+                heap.push(Reverse((new_dist, neighbor)));
             }
         }
     }
 
     // Восстановление пути из массива prev
-    if dist[end] == usize::MAX {
+    if dist[end.index()].is_none() {
         return Vec::new(); // Путь не найден
     }
 
-    // Восстанавливаем путь до города в обратном порядке
+    // Восстанавливаем путь до вершины в обратном порядке
     let mut path = Vec::new();
     let mut current = end;
-    while let Some(prev_city) = prev[current] {
+    while let Some(prev_node) = prev[current.index()] {
         path.push(current);
-        current = prev_city;
+        current = prev_node;
     }
-    path.push(start_idx);
+    path.push(start);
     path.reverse();
 
     path
 }
 
 #[test]
+#[allow(clippy::vec_init_then_push)]
 fn test_dijkstra() {
     let mut city_map: CityMap = Default::default();
 
@@ -102,9 +146,45 @@ fn test_dijkstra() {
     city_map.push(("Новокузнецк".to_string(), vec![(7, 260), (9, 222)])); // 8
     city_map.push(("Кемерово".to_string(), vec![(8, 222), (1, 265)])); // 9
 
-    assert_eq!(dijkstra(&city_map, 0, 2), vec![0, 1, 2]);
-    assert_eq!(dijkstra(&city_map, 8, 6), vec![8, 7, 0, 3, 6]);
-    assert_eq!(dijkstra(&city_map, 0, 9), vec![0, 1, 9]);
-    assert_eq!(dijkstra(&city_map, 0, 5), vec![0, 1, 4, 5]);
-    assert_eq!(dijkstra(&city_map, 0, 6), vec![0, 3, 6]);
+    let graph = Graph::from(&city_map);
+    let path = |start: usize, end: usize| -> Vec<usize> {
+        dijkstra(&graph, NodeId::new(start), NodeId::new(end))
+            .into_iter()
+            .map(NodeId::index)
+            .collect()
+    };
+
+    assert_eq!(path(0, 2), vec![0, 1, 2]);
+    assert_eq!(path(8, 6), vec![8, 7, 0, 3, 6]);
+    assert_eq!(path(0, 9), vec![0, 1, 9]);
+    assert_eq!(path(0, 5), vec![0, 1, 4, 5]);
+    assert_eq!(path(0, 6), vec![0, 3, 6]);
+}
+
+#[test]
+fn test_dijkstra_generic() {
+    // Ориентированный граф: обратного пути нет.
+    let mut graph: Graph<&str, u8> = Graph::directed();
+    let [a, b, c] = ["a", "b", "c"].map(|name| graph.add_node(name));
+    graph.add_edge(a, b, 200);
+    graph.add_edge(b, c, 100);
+    let direct = graph.add_edge(a, c, 255);
+    assert_eq!(dijkstra(&graph, a, c), [a, c]);
+    assert_eq!(dijkstra(&graph, c, a), []);
+    assert_eq!(dijkstra(&graph, a, a), [a]);
+
+    // Путь a → b → c переполняет u8 и не рассматривается.
+    graph.remove_edge(direct);
+    assert_eq!(dijkstra(&graph, a, c), []);
+
+    // После удаления вершины идентификаторы остальных прежние.
+    let mut graph: Graph<(), u64> = Graph::undirected();
+    let [a, b, c, d] = [(); 4].map(|()| graph.add_node(()));
+    graph.add_edge(a, b, 1);
+    graph.add_edge(b, d, 1);
+    graph.add_edge(a, c, 1);
+    graph.add_edge(c, d, 5);
+    assert_eq!(dijkstra(&graph, a, d), [a, b, d]);
+    graph.remove_node(b);
+    assert_eq!(dijkstra(&graph, a, d), [a, c, d]);
 }
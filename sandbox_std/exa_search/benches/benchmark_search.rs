@@ -0,0 +1,185 @@
+//! Сравнение стратегий поиска на отсортированных массивах разного размера.
+//!
+//! Каждый замер — серия из [`LOOKUPS`] поисков случайных значений, половина которых
+//! есть в массиве. Группа `distribution` сравнивает интерполяционный поиск с бинарным
+//! на равномерных и неравномерных ключах, группа `cache` — раскладки для кэша на
+//! массивах от размера кэша L1 до размера, намного превышающего L3.
+//!
+//! ```bash
+//! cargo bench -p exa_search --bench benchmark_search
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use exa_search::{
+    BinarySearch, BranchlessSearch, ExponentialSearch, EytzingerIndex, InterpolationSearch,
+    Searcher, searchers,
+};
+use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+/// Размеры массива.
+const SIZES: [usize; 4] = [100, 10_000, 1_000_000, 10_000_000];
+/// Наибольший размер массива для линейного поиска.
+const LINEAR_MAX: usize = 10_000;
+/// Количество поисков в одном замере.
+const LOOKUPS: usize = 1000;
+
+fn benchmark_search(cri: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    let mut group = cri.benchmark_group("search");
+
+    for size in SIZES {
+        // Чётные числа: нечётные значения в массиве отсутствуют.
+        let arr: Vec<u64> = (0..size as u64).map(|i| i * 2).collect();
+        let targets: Vec<u64> = (0..LOOKUPS)
+            .map(|_| rng.random_range(0..2 * size as u64))
+            .collect();
+        group.throughput(Throughput::Elements(LOOKUPS as u64));
+
+        let all = searchers().into_iter().chain([&InterpolationSearch as _]);
+        for searcher in all {
+            if searcher.name() == "linear" && size > LINEAR_MAX {
+                continue;
+            }
+            group.bench_with_input(
+                BenchmarkId::new(searcher.name(), size),
+                &targets,
+                |b, targets| {
+                    b.iter(|| {
+                        for target in targets {
+                            black_box(searcher.search(black_box(&arr), target));
+                        }
+                    })
+                },
+            );
+        }
+        group.bench_with_input(BenchmarkId::new("std", size), &targets, |b, targets| {
+            b.iter(|| {
+                for target in targets {
+                    black_box(black_box(&arr).binary_search(target).ok());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Размер массива в группе `distribution`.
+const DISTRIBUTION_SIZE: usize = 1_000_000;
+
+fn benchmark_distribution(cri: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    let mut group = cri.benchmark_group("distribution");
+    group.throughput(Throughput::Elements(LOOKUPS as u64));
+
+    let size = DISTRIBUTION_SIZE as u64;
+    let distributions: [(&str, Vec<u64>); 3] = [
+        ("uniform", (0..size).map(|i| i * 2).collect()),
+        // Равномерные со случайными промежутками, как столбец идентификаторов.
+        ("uniform_gaps", {
+            let mut next = 0;
+            (0..size)
+                .map(|_| {
+                    next += rng.random_range(1..8);
+                    next
+                })
+                .collect()
+        }),
+        // Кубы: интерполяция сильно ошибается и переходит на бинарные шаги.
+        ("cubic", (0..size).map(|i| i * i * i).collect()),
+    ];
+    let strategies: [&dyn Searcher<u64>; 3] =
+        [&InterpolationSearch, &ExponentialSearch, &BinarySearch];
+
+    for (name, arr) in &distributions {
+        let targets: Vec<u64> = (0..LOOKUPS)
+            .map(|i| {
+                let element = arr[rng.random_range(0..arr.len())];
+                // Половина значений отсутствует (кроме совпадений с соседями).
+                element + (i % 2) as u64
+            })
+            .collect();
+
+        for searcher in strategies {
+            group.bench_with_input(
+                BenchmarkId::new(searcher.name(), name),
+                &targets,
+                |b, targets| {
+                    b.iter(|| {
+                        for target in targets {
+                            black_box(searcher.search(black_box(arr), target));
+                        }
+                    })
+                },
+            );
+        }
+        group.bench_with_input(BenchmarkId::new("std", name), &targets, |b, targets| {
+            b.iter(|| {
+                for target in targets {
+                    black_box(black_box(arr).binary_search(target).ok());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Размеры массива `u32` в группе `cache`: 4 КиБ, 256 КиБ, 4 МиБ, 32 МиБ и 128 МиБ.
+const CACHE_SIZES: [usize; 5] = [1 << 10, 1 << 16, 1 << 20, 1 << 23, 1 << 25];
+
+fn benchmark_cache(cri: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    let mut group = cri.benchmark_group("cache");
+    group.throughput(Throughput::Elements(LOOKUPS as u64));
+
+    for size in CACHE_SIZES {
+        let arr: Vec<u32> = (0..size as u32).map(|i| i * 2).collect();
+        let index = EytzingerIndex::new(&arr);
+        let targets: Vec<u32> = (0..LOOKUPS)
+            .map(|_| rng.random_range(0..2 * size as u32))
+            .collect();
+
+        let strategies: [&dyn Searcher<u32>; 2] = [&BinarySearch, &BranchlessSearch];
+        for searcher in strategies {
+            group.bench_with_input(
+                BenchmarkId::new(searcher.name(), size),
+                &targets,
+                |b, targets| {
+                    b.iter(|| {
+                        for target in targets {
+                            black_box(searcher.search(black_box(&arr), target));
+                        }
+                    })
+                },
+            );
+        }
+        group.bench_with_input(
+            BenchmarkId::new("eytzinger", size),
+            &targets,
+            |b, targets| {
+                b.iter(|| {
+                    for target in targets {
+                        black_box(black_box(&index).search(target));
+                    }
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("std", size), &targets, |b, targets| {
+            b.iter(|| {
+                for target in targets {
+                    black_box(black_box(&arr).binary_search(target).ok());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_search,
+    benchmark_distribution,
+    benchmark_cache
+);
+criterion_main!(benches);
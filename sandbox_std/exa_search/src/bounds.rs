@@ -0,0 +1,272 @@
+//! Границы диапазона равных элементов в отсортированном срезе.
+//!
+//! В отличие от [`crate::binary_search`], который при повторах возвращает любой из равных
+//! элементов, эти функции возвращают границы: [`lower_bound`] — первый элемент не меньше
+//! искомого, [`upper_bound`] — первый элемент больше искомого, [`equal_range`] — весь
+//! диапазон равных. Все они построены на [`partition_point`].
+//!
+//! Варианты `_by` принимают функцию, которая сравнивает элемент с искомым (как
+//! [`slice::binary_search_by`]), варианты `_by_key` — функцию, извлекающую ключ.
+
+use std::{cmp::Ordering, ops::Range};
+
+/// Точка разбиения
+/// Срез должен быть разбит предикатом: сначала все элементы, для которых `pred` истинен,
+/// затем все, для которых ложен. Возвращает индекс первого элемента второй группы
+/// (длину среза, если такого нет).
+///
+/// Характеристики:
+///  * Сложность по времени: O(log n).
+///  * Сложность по памяти: O(1).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::partition_point;
+///
+/// let arr = [1, 2, 3, 3, 5, 6, 7];
+/// assert_eq!(partition_point(&arr, |&x| x < 5), 4);
+/// assert_eq!(partition_point(&arr, |_| true), 7);
+/// assert_eq!(partition_point(&arr, |_| false), 0);
+/// ```
+pub fn partition_point<T, P>(arr: &[T], mut pred: P) -> usize
+where
+    P: FnMut(&T) -> bool,
+{
+    let mut left = 0;
+    let mut right = arr.len();
+
+    while left < right {
+        let mid = left + (right - left) / 2;
+        if pred(&arr[mid]) {
+            left = mid + 1;
+        } else {
+            right = mid;
+        }
+    }
+
+    left
+}
+
+/// Нижняя граница (Lower Bound)
+/// Индекс первого элемента, не меньшего `target`. Если все элементы меньше — длина среза.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::lower_bound;
+///
+/// let arr = [1, 2, 2, 2, 3];
+/// assert_eq!(lower_bound(&arr, &2), 1);
+/// assert_eq!(lower_bound(&arr, &0), 0);
+/// assert_eq!(lower_bound(&arr, &4), 5);
+/// ```
+pub fn lower_bound<T: Ord>(arr: &[T], target: &T) -> usize {
+    lower_bound_by(arr, |element| element.cmp(target))
+}
+
+/// Нижняя граница с пользовательской функцией сравнения.
+/// `compare` возвращает порядок элемента относительно искомого.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::lower_bound_by;
+///
+/// let arr = [5, 4, 4, 1];
+/// assert_eq!(lower_bound_by(&arr, |x| 4.cmp(x)), 1);
+/// ```
+pub fn lower_bound_by<T, F>(arr: &[T], mut compare: F) -> usize
+where
+    F: FnMut(&T) -> Ordering,
+{
+    partition_point(arr, |element| compare(element) == Ordering::Less)
+}
+
+/// Нижняя граница по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::lower_bound_by_key;
+///
+/// let arr = [(1, 'a'), (2, 'b'), (2, 'c'), (3, 'd')];
+/// assert_eq!(lower_bound_by_key(&arr, &2, |&(n, _)| n), 1);
+/// ```
+pub fn lower_bound_by_key<T, K, F>(arr: &[T], target: &K, mut key: F) -> usize
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    lower_bound_by(arr, |element| key(element).cmp(target))
+}
+
+/// Верхняя граница (Upper Bound)
+/// Индекс первого элемента, большего `target`. Если таких нет — длина среза.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::upper_bound;
+///
+/// let arr = [1, 2, 2, 2, 3];
+/// assert_eq!(upper_bound(&arr, &2), 4);
+/// assert_eq!(upper_bound(&arr, &0), 0);
+/// assert_eq!(upper_bound(&arr, &3), 5);
+/// ```
+pub fn upper_bound<T: Ord>(arr: &[T], target: &T) -> usize {
+    upper_bound_by(arr, |element| element.cmp(target))
+}
+
+/// Верхняя граница с пользовательской функцией сравнения.
+/// `compare` возвращает порядок элемента относительно искомого.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::upper_bound_by;
+///
+/// let arr = [5, 4, 4, 1];
+/// assert_eq!(upper_bound_by(&arr, |x| 4.cmp(x)), 3);
+/// ```
+pub fn upper_bound_by<T, F>(arr: &[T], mut compare: F) -> usize
+where
+    F: FnMut(&T) -> Ordering,
+{
+    partition_point(arr, |element| compare(element) != Ordering::Greater)
+}
+
+/// Верхняя граница по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::upper_bound_by_key;
+///
+/// let arr = [(1, 'a'), (2, 'b'), (2, 'c'), (3, 'd')];
+/// assert_eq!(upper_bound_by_key(&arr, &2, |&(n, _)| n), 3);
+/// ```
+pub fn upper_bound_by_key<T, K, F>(arr: &[T], target: &K, mut key: F) -> usize
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    upper_bound_by(arr, |element| key(element).cmp(target))
+}
+
+/// Диапазон равных (Equal Range)
+/// Индексы всех элементов, равных `target`: `lower_bound..upper_bound`.
+/// Если таких нет, диапазон пуст и начинается там, куда можно вставить `target`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::equal_range;
+///
+/// let arr = [1, 2, 2, 2, 3];
+/// assert_eq!(equal_range(&arr, &2), 1..4);
+/// assert_eq!(equal_range(&arr, &5), 5..5);
+/// assert_eq!(arr[equal_range(&arr, &2)], [2, 2, 2]);
+/// ```
+pub fn equal_range<T: Ord>(arr: &[T], target: &T) -> Range<usize> {
+    equal_range_by(arr, |element| element.cmp(target))
+}
+
+/// Диапазон равных с пользовательской функцией сравнения.
+/// `compare` возвращает порядок элемента относительно искомого.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::equal_range_by;
+///
+/// let arr = [5, 4, 4, 1];
+/// assert_eq!(equal_range_by(&arr, |x| 4.cmp(x)), 1..3);
+/// ```
+pub fn equal_range_by<T, F>(arr: &[T], mut compare: F) -> Range<usize>
+where
+    F: FnMut(&T) -> Ordering,
+{
+    let start = lower_bound_by(arr, &mut compare);
+    // Верхняя граница не левее нижней, поэтому ищем только справа от неё.
+    let end = start + upper_bound_by(&arr[start..], compare);
+    start..end
+}
+
+/// Диапазон равных по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::equal_range_by_key;
+///
+/// let arr = [(1, 'a'), (2, 'b'), (2, 'c'), (3, 'd')];
+/// let range = equal_range_by_key(&arr, &2, |&(n, _)| n);
+/// assert_eq!(arr[range], [(2, 'b'), (2, 'c')]);
+/// ```
+pub fn equal_range_by_key<T, K, F>(arr: &[T], target: &K, mut key: F) -> Range<usize>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    equal_range_by(arr, |element| key(element).cmp(target))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    /// Отсортированный массив с большим количеством повторов.
+    fn duplicates(len: usize, distinct: u32) -> Vec<u32> {
+        let mut rng = StdRng::seed_from_u64(0x1357_9bdf);
+        let mut arr: Vec<u32> = (0..len).map(|_| rng.random_range(0..distinct)).collect();
+        arr.sort();
+        arr
+    }
+
+    #[test]
+    fn test_duplicates() {
+        for len in [0, 1, 2, 10, 100, 1000] {
+            for distinct in [1, 2, 5, 50] {
+                let arr = duplicates(len, distinct);
+                for target in 0..=distinct {
+                    let lower = arr.iter().filter(|&&x| x < target).count();
+                    let upper = arr.iter().filter(|&&x| x <= target).count();
+                    assert_eq!(lower_bound(&arr, &target), lower);
+                    assert_eq!(upper_bound(&arr, &target), upper);
+                    assert_eq!(equal_range(&arr, &target), lower..upper);
+                    assert_eq!(partition_point(&arr, |&x| x < target), lower);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_by_key() {
+        // Пары (ключ, позиция): в диапазон должны попасть все элементы с ключом.
+        let arr: Vec<(u32, usize)> = duplicates(500, 7).into_iter().zip(0..).collect();
+        for target in 0..8 {
+            let range = equal_range_by_key(&arr, &target, |&(key, _)| key);
+            let expected: Vec<usize> = arr
+                .iter()
+                .filter(|&&(key, _)| key == target)
+                .map(|&(_, position)| position)
+                .collect();
+            let found: Vec<usize> = arr[range].iter().map(|&(_, position)| position).collect();
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn test_descending() {
+        let mut arr = duplicates(300, 10);
+        arr.reverse();
+        for target in 0..=10 {
+            let range = equal_range_by(&arr, |x| target.cmp(x));
+            assert!(arr[range.clone()].iter().all(|&x| x == target));
+            assert_eq!(range.len(), arr.iter().filter(|&&x| x == target).count());
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Бинарный поиск без ветвлений (Branchless Binary Search).
+
+use std::hint::select_unpredictable;
+
+/// Нижняя граница без ветвлений
+/// Индекс первого элемента, не меньшего `target` (длина среза, если такого нет).
+/// Диапазон на каждом шаге сокращается вдвое независимо от результата сравнения, а
+/// результат выбирается условной пересылкой, а не переходом: процессору нечего
+/// предсказывать, поэтому нет сбросов конвейера на случайных запросах.
+///
+/// Характеристики:
+///  * Сложность по времени: ровно ⌈log₂(n + 1)⌉ сравнений.
+///  * Сложность по памяти: O(1).
+///  * Требование: массив должен быть отсортирован.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::branchless_lower_bound;
+///
+/// let arr = [1, 2, 2, 2, 3];
+/// assert_eq!(branchless_lower_bound(&arr, &2), 1);
+/// assert_eq!(branchless_lower_bound(&arr, &0), 0);
+/// assert_eq!(branchless_lower_bound(&arr, &4), 5);
+/// ```
+pub fn branchless_lower_bound<T: Ord>(arr: &[T], target: &T) -> usize {
+    if arr.is_empty() {
+        return 0;
+    }
+
+    // Ответ в `base..=base + len`; сокращаем `len` до одного элемента.
+    let mut base = 0;
+    let mut len = arr.len();
+    while len > 1 {
+        let half = len / 2;
+        base = select_unpredictable(arr[base + half - 1] < *target, base + half, base);
+        len -= half;
+    }
+    base + usize::from(arr[base] < *target)
+}
+
+/// Бинарный поиск без ветвлений (Branchless Binary Search)
+/// Принцип работы: находится нижняя граница [`branchless_lower_bound`], затем
+/// проверяется, равен ли элемент на ней искомому.
+/// Когда использовать:
+///  * частые поиски случайных значений в небольших и средних таблицах, где
+///    стоимость неверно предсказанных переходов заметна.
+///
+/// Характеристики:
+///  * Сложность по времени: O(log n).
+///  * Сложность по памяти: O(1).
+///  * Требование: массив должен быть отсортирован.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::branchless_binary_search;
+///
+/// let arr = [1, 2, 3, 4, 5];
+/// assert_eq!(branchless_binary_search(&arr, &3), Some(2));
+/// ```
+///
+/// ```
+/// use exa_search::branchless_binary_search;
+///
+/// let arr = [10, 20, 30];
+/// assert_eq!(branchless_binary_search(&arr, &25), None);
+/// ```
+///
+/// ```
+/// use exa_search::branchless_binary_search;
+///
+/// // Из равных элементов возвращается первый.
+/// let arr = [1, 2, 2, 2, 3];
+/// assert_eq!(branchless_binary_search(&arr, &2), Some(1));
+/// ```
+pub fn branchless_binary_search<T: Ord>(arr: &[T], target: &T) -> Option<usize> {
+    let index = branchless_lower_bound(arr, target);
+    arr.get(index)
+        .filter(|element| *element == target)
+        .map(|_| index)
+}
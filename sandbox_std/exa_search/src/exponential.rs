@@ -0,0 +1,124 @@
+//! Экспоненциальный поиск (Exponential Search, galloping).
+
+use std::cmp::Ordering;
+
+use crate::{binary_search, lower_bound};
+
+/// Экспоненциальный поиск (Exponential Search)
+/// Принцип работы: проверяются элементы с индексами 0, 1, 3, 7, ..., пока не найдётся
+/// элемент не меньше искомого, затем в последнем промежутке выполняется бинарный поиск.
+/// Когда использовать:
+///  * массив отсортирован, а искомый элемент, скорее всего, близко к началу;
+///  * длина данных заранее неизвестна, см. [`exponential_search_unbounded`].
+///
+/// Характеристики:
+///  * Сложность по времени: O(log i), где i — позиция искомого элемента.
+///  * Сложность по памяти: O(1).
+///  * Требование: массив должен быть отсортирован.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::exponential_search;
+///
+/// let arr = [1, 2, 3, 4, 5];
+/// assert_eq!(exponential_search(&arr, &3), Some(2));
+/// ```
+///
+/// ```
+/// use exa_search::exponential_search;
+///
+/// let arr: Vec<u32> = (0..1000).map(|i| i * 3).collect();
+/// assert_eq!(exponential_search(&arr, &9), Some(3));
+/// assert_eq!(exponential_search(&arr, &10), None);
+/// ```
+pub fn exponential_search<T: Ord>(arr: &[T], target: &T) -> Option<usize> {
+    let len = arr.len();
+    let mut bound = 1;
+    while bound <= len && arr[bound - 1] < *target {
+        bound *= 2;
+    }
+
+    // Искомый элемент в `arr[bound / 2 - 1 .. bound - 1]`, если есть.
+    let start = bound / 2;
+    let end = bound.min(len);
+    binary_search(&arr[start.saturating_sub(1)..end], target)
+        .map(|index| index + start.saturating_sub(1))
+}
+
+/// Нижняя граница экспоненциальным поиском
+/// Индекс первого элемента, не меньшего `target` (длина среза, если такого нет), за
+/// O(log i), где i — ответ. Основа «галопирующих» операций над множествами, в которых
+/// следующий искомый элемент обычно недалеко от текущей позиции.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::exponential_lower_bound;
+///
+/// let arr = [1, 2, 2, 2, 3];
+/// assert_eq!(exponential_lower_bound(&arr, &2), 1);
+/// assert_eq!(exponential_lower_bound(&arr, &0), 0);
+/// assert_eq!(exponential_lower_bound(&arr, &4), 5);
+/// ```
+pub fn exponential_lower_bound<T: Ord>(arr: &[T], target: &T) -> usize {
+    let len = arr.len();
+    let mut bound = 1;
+    while bound <= len && arr[bound - 1] < *target {
+        bound *= 2;
+    }
+
+    // `arr[bound / 2 - 1] < target`, а `arr[bound - 1]` (если есть) не меньше.
+    let start = bound / 2;
+    start + lower_bound(&arr[start..bound.min(len)], target)
+}
+
+/// Экспоненциальный поиск в последовательности неизвестной длины.
+/// `get(i)` возвращает `i`-й элемент или `None`, если последовательность короче.
+/// Элементы должны идти по возрастанию. Возвращает индекс элемента, равного `target`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::exponential_search_unbounded;
+///
+/// // Квадраты чисел, длина известна только источнику.
+/// let squares = |i: usize| (i < 1_000_000).then(|| i * i);
+/// assert_eq!(exponential_search_unbounded(squares, &144), Some(12));
+/// assert_eq!(exponential_search_unbounded(squares, &145), None);
+/// ```
+pub fn exponential_search_unbounded<T, F>(mut get: F, target: &T) -> Option<usize>
+where
+    T: Ord,
+    F: FnMut(usize) -> Option<T>,
+{
+    // Найдём `end`, такой что элемент `end` не меньше искомого или отсутствует.
+    let mut start = 0;
+    let mut end = 0;
+    loop {
+        match get(end) {
+            Some(element) => match element.cmp(target) {
+                Ordering::Less => {}
+                Ordering::Equal => return Some(end),
+                Ordering::Greater => break,
+            },
+            None => break,
+        }
+        start = end + 1;
+        end = end * 2 + 1;
+    }
+
+    // Бинарный поиск в `start..end`: все элементы там существуют, элемент `end` — граница.
+    while start < end {
+        let mid = start + (end - start) / 2;
+        match get(mid) {
+            Some(element) => match element.cmp(target) {
+                Ordering::Equal => return Some(mid),
+                Ordering::Less => start = mid + 1,
+                Ordering::Greater => end = mid,
+            },
+            None => end = mid,
+        }
+    }
+    None
+}
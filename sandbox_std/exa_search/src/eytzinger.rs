@@ -0,0 +1,250 @@
+//! Индекс в раскладке Эйтцингера (Eytzinger Layout).
+//!
+//! Отсортированный массив переупорядочивается в порядке обхода в ширину неявного
+//! двоичного дерева поиска: корень в ячейке 1, потомки узла `k` — в ячейках `2k` и
+//! `2k + 1`. Первые уровни дерева, которые проходит каждый поиск, лежат рядом в
+//! начале массива и не покидают кэш, а потомки узла на несколько уровней вниз занимают
+//! одну кэш-линию, поэтому их можно заранее подгрузить (prefetch), пока идут сравнения.
+//! На массивах больше кэша L2/L3 это заметно быстрее обычного бинарного поиска.
+
+/// Размер кэш-линии в байтах.
+const CACHE_LINE: usize = 64;
+
+/// Отсортированный набор элементов, переразложенный для быстрого поиска.
+///
+/// Позиции, которые возвращают методы, — индексы в исходном отсортированном срезе.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::EytzingerIndex;
+///
+/// let sorted = [2, 3, 5, 7, 11, 13, 17];
+/// let index = EytzingerIndex::new(&sorted);
+/// assert_eq!(index.search(&11), Some(4));
+/// assert_eq!(index.search(&12), None);
+/// assert_eq!(index.lower_bound(&12), 5);
+/// assert!(index.contains(&2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EytzingerIndex<T> {
+    /// Узел `k` (нумерация с единицы) хранится в `data[k - 1]`.
+    data: Vec<T>,
+}
+
+impl<T: Ord> EytzingerIndex<T> {
+    /// Строит индекс по отсортированному срезу за O(n).
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// use exa_search::EytzingerIndex;
+    ///
+    /// let index = EytzingerIndex::new(&["a", "b", "c"]);
+    /// assert_eq!(index.len(), 3);
+    /// assert_eq!(index.search(&"c"), Some(2));
+    /// ```
+    pub fn new(sorted: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        debug_assert!(sorted.is_sorted(), "срез должен быть отсортирован");
+        let len = sorted.len();
+        let data = (1..=len).map(|k| sorted[rank(k, len)].clone()).collect();
+        Self { data }
+    }
+
+    /// Количество элементов.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Пуст ли индекс.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Есть ли в индексе элемент, равный `target`.
+    pub fn contains(&self, target: &T) -> bool {
+        self.search(target).is_some()
+    }
+
+    /// Позиция элемента, равного `target`, в исходном срезе.
+    /// Если равных элементов несколько, возвращает первый.
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// use exa_search::EytzingerIndex;
+    ///
+    /// let index = EytzingerIndex::new(&[1, 2, 2, 2, 3]);
+    /// assert_eq!(index.search(&2), Some(1));
+    /// assert_eq!(index.search(&4), None);
+    /// ```
+    pub fn search(&self, target: &T) -> Option<usize> {
+        let node = self.lower_bound_node(target);
+        (node != 0 && self.data[node - 1] == *target).then(|| rank(node, self.len()))
+    }
+
+    /// Нижняя граница: позиция первого элемента, не меньшего `target`, в исходном срезе
+    /// (длина, если такого нет).
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// use exa_search::EytzingerIndex;
+    ///
+    /// let index = EytzingerIndex::new(&[10, 20, 30]);
+    /// assert_eq!(index.lower_bound(&5), 0);
+    /// assert_eq!(index.lower_bound(&20), 1);
+    /// assert_eq!(index.lower_bound(&35), 3);
+    /// ```
+    pub fn lower_bound(&self, target: &T) -> usize {
+        match self.lower_bound_node(target) {
+            0 => self.len(),
+            node => rank(node, self.len()),
+        }
+    }
+
+    /// Элементы в исходном отсортированном порядке.
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// use exa_search::EytzingerIndex;
+    ///
+    /// let index = EytzingerIndex::new(&[1, 2, 3, 4]);
+    /// assert_eq!(index.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let len = self.len();
+        // Самый левый узел поддерева `k`.
+        let leftmost = move |mut k: usize| {
+            while 2 * k <= len {
+                k *= 2;
+            }
+            k
+        };
+        // Следующий узел: самый левый в правом поддереве, а если его нет — первый предок,
+        // в левом поддереве которого мы были.
+        let next = move |&k: &usize| {
+            if 2 * k < len {
+                Some(leftmost(2 * k + 1))
+            } else {
+                Some(k >> (k.trailing_ones() + 1)).filter(|&parent| parent != 0)
+            }
+        };
+        let first = (len > 0).then(|| leftmost(1));
+        std::iter::successors(first, next).map(|k| &self.data[k - 1])
+    }
+
+    /// Узел нижней границы или 0, если все элементы меньше `target`.
+    fn lower_bound_node(&self, target: &T) -> usize {
+        // Потомки узла `k` через `depth` уровней — узлы `k << depth ..`, они занимают
+        // одну кэш-линию. Их подгружаем заранее, пока спускаемся к ним.
+        let depth = (CACHE_LINE / size_of::<T>().max(1)).max(1).ilog2();
+        let len = self.len();
+        let base = self.data.as_ptr();
+
+        let mut k = 1;
+        while k <= len {
+            prefetch(base.wrapping_add((k << depth).wrapping_sub(1)));
+            k = 2 * k + usize::from(self.data[k - 1] < *target);
+        }
+        // Последний поворот налево — узел ответа: отбрасываем хвост поворотов направо
+        // (единицы) и сам поворот налево.
+        k >> (k.trailing_ones() + 1)
+    }
+}
+
+impl<T: Ord + Clone> FromIterator<T> for EytzingerIndex<T> {
+    /// Собирает индекс из элементов в любом порядке.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut sorted: Vec<T> = iter.into_iter().collect();
+        sorted.sort();
+        Self::new(&sorted)
+    }
+}
+
+/// Позиция узла `k` в отсортированном порядке для дерева из `len` узлов.
+///
+/// Если бы последний уровень был заполнен, позиция вычислялась бы по глубине узла.
+/// Отсутствующие листья последнего уровня стоят в отсортированном порядке через один,
+/// поэтому вычитаем те из них, что предшествуют узлу.
+fn rank(k: usize, len: usize) -> usize {
+    let height = len.ilog2();
+    let depth = k.ilog2();
+    let full = ((2 * (k - (1 << depth)) + 1) << (height - depth)) - 1;
+    // Листьев последнего уровня, которые есть в дереве.
+    let present = len + 1 - (1 << height);
+    full - full.div_ceil(2).saturating_sub(present)
+}
+
+/// Подсказка процессору загрузить кэш-линию с `ptr`; указатель может быть невалидным.
+#[inline(always)]
+fn prefetch<T>(ptr: *const T) {
+    #[cfg(target_arch = "x86_64")]
+    // SAFETY: prefetch не обращается к памяти и не сообщает об ошибках даже для
+    // невалидного адреса.
+    unsafe {
+        use std::arch::x86_64::{_MM_HINT_T0, _mm_prefetch};
+        _mm_prefetch::<_MM_HINT_T0>(ptr.cast());
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = ptr;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{branchless_binary_search, branchless_lower_bound};
+
+    /// Раскладка построением дерева рекурсивным обходом в порядке возрастания.
+    fn layout(sorted: &[u32]) -> Vec<u32> {
+        fn fill(k: usize, sorted: &[u32], next: &mut usize, data: &mut [u32]) {
+            if k <= data.len() {
+                fill(2 * k, sorted, next, data);
+                data[k - 1] = sorted[*next];
+                *next += 1;
+                fill(2 * k + 1, sorted, next, data);
+            }
+        }
+        let mut data = vec![0; sorted.len()];
+        fill(1, sorted, &mut 0, &mut data);
+        data
+    }
+
+    #[test]
+    fn test_layout() {
+        for len in 0..300 {
+            let sorted: Vec<u32> = (0..len).collect();
+            let index = EytzingerIndex::new(&sorted);
+            assert_eq!(index.data, layout(&sorted), "len = {len}");
+            assert!(index.iter().eq(&sorted), "len = {len}");
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        for len in 0..200 {
+            // Значения `0, 0, 2, 2, 4, ...`: повторы и пропуски.
+            let sorted: Vec<u32> = (0..len).map(|i| i / 2 * 2).collect();
+            let index: EytzingerIndex<u32> = sorted.iter().rev().copied().collect();
+            for target in 0..len + 2 {
+                let lower = sorted.partition_point(|&x| x < target);
+                let expected = sorted.contains(&target).then_some(lower);
+                assert_eq!(index.lower_bound(&target), lower, "len = {len}, {target}");
+                assert_eq!(index.search(&target), expected, "len = {len}, {target}");
+                assert_eq!(branchless_lower_bound(&sorted, &target), lower);
+                assert_eq!(branchless_binary_search(&sorted, &target), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_sized() {
+        let index = EytzingerIndex::new(&[(); 10]);
+        assert_eq!(index.search(&()), Some(0));
+        assert_eq!(index.iter().count(), 10);
+    }
+}
@@ -0,0 +1,72 @@
+//! Поиск Фибоначчи (Fibonacci Search).
+
+use std::cmp::Ordering;
+
+/// Поиск Фибоначчи (Fibonacci Search)
+/// Принцип работы: как бинарный поиск, но диапазон делится в отношении соседних чисел
+/// Фибоначчи. Индексы вычисляются сложением и вычитанием, без деления.
+/// Когда использовать:
+///  * массив отсортирован;
+///  * деление дорого или обращения к близким элементам дешевле, чем к далёким.
+///
+/// Характеристики:
+///  * Сложность по времени: O(log n).
+///  * Сложность по памяти: O(1).
+///  * Требование: массив должен быть отсортирован.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::fibonacci_search;
+///
+/// let arr = [1, 2, 3, 4, 5];
+/// assert_eq!(fibonacci_search(&arr, &3), Some(2));
+/// ```
+///
+/// ```
+/// use exa_search::fibonacci_search;
+///
+/// let arr = [10, 22, 35, 40, 45, 50, 80, 82, 85, 90, 100];
+/// assert_eq!(fibonacci_search(&arr, &85), Some(8));
+/// assert_eq!(fibonacci_search(&arr, &11), None);
+/// ```
+pub fn fibonacci_search<T: Ord>(arr: &[T], target: &T) -> Option<usize> {
+    let len = arr.len();
+    if len == 0 {
+        return None;
+    }
+
+    // Наименьшее число Фибоначчи `fib`, не меньшее длины, и два предыдущих.
+    let (mut fib2, mut fib1) = (0, 1);
+    let mut fib = fib2 + fib1;
+    while fib < len {
+        fib2 = fib1;
+        fib1 = fib;
+        fib = fib2 + fib1;
+    }
+
+    // Все элементы левее `start` меньше искомого.
+    let mut start = 0;
+    while fib > 1 {
+        let index = (start + fib2 - 1).min(len - 1);
+        match arr[index].cmp(target) {
+            Ordering::Less => {
+                // Остаётся правая часть длиной `fib1`.
+                fib = fib1;
+                fib1 = fib2;
+                fib2 = fib - fib1;
+                start = index + 1;
+            }
+            Ordering::Greater => {
+                // Остаётся левая часть длиной `fib2`.
+                fib = fib2;
+                fib1 -= fib2;
+                fib2 = fib - fib1;
+            }
+            Ordering::Equal => return Some(index),
+        }
+    }
+
+    // Мог остаться один непроверенный элемент.
+    (fib1 == 1 && start < len && arr[start] == *target).then_some(start)
+}
@@ -0,0 +1,178 @@
+//! Нечёткий поиск (Fuzzy Search).
+//!
+//! [`crate::linear_search`] находит только точные совпадения. Здесь собраны средства
+//! для поиска с опечатками:
+//!  * редакционные расстояния между строками: [`levenshtein`], [`osa_distance`],
+//!    [`damerau_levenshtein`];
+//!  * приближённый поиск подстроки с ограничением на число ошибок: [`MyersFinder`];
+//!  * индекс словаря для подсказок «возможно, вы имели в виду»: [`BkTree`].
+
+pub mod bk_tree;
+pub mod distance;
+pub mod myers;
+
+pub use bk_tree::BkTree;
+pub use distance::{
+    damerau_levenshtein, damerau_levenshtein_slice, levenshtein, levenshtein_slice, osa_distance,
+    osa_distance_slice,
+};
+pub use myers::{FuzzyMatch, MyersFinder, MyersMatches};
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    /// Псевдослучайная строка из первых `alphabet` строчных букв.
+    fn word(len: usize, alphabet: u8, rng: &mut StdRng) -> String {
+        (0..len)
+            .map(|_| (b'a' + rng.random_range(0..alphabet)) as char)
+            .collect()
+    }
+
+    #[test]
+    fn test_distances() {
+        for (a, b, lev, osa, dl) in [
+            ("", "", 0, 0, 0),
+            ("abc", "", 3, 3, 3),
+            ("kitten", "sitting", 3, 3, 3),
+            ("ab", "ba", 2, 1, 1),
+            ("CA", "ABC", 3, 3, 2),
+            ("съешь", "сьешь", 1, 1, 1),
+            ("abcdef", "badcfe", 4, 3, 3),
+        ] {
+            assert_eq!(levenshtein(a, b), lev, "{a}, {b}");
+            assert_eq!(osa_distance(a, b), osa, "{a}, {b}");
+            assert_eq!(damerau_levenshtein(a, b), dl, "{a}, {b}");
+        }
+    }
+
+    #[test]
+    fn test_metric_properties() {
+        let mut rng = StdRng::seed_from_u64(0x1234_5678);
+        let words: Vec<String> = (0..40).map(|i| word(i % 7, 3, &mut rng)).collect();
+        for a in &words {
+            for b in &words {
+                let (lev, osa, dl) = (
+                    levenshtein(a, b),
+                    osa_distance(a, b),
+                    damerau_levenshtein(a, b),
+                );
+                assert_eq!(lev, levenshtein(b, a));
+                assert_eq!(dl, damerau_levenshtein(b, a));
+                assert!(dl <= osa && osa <= lev, "{a}, {b}");
+                assert_eq!(lev == 0, a == b);
+                for c in &words {
+                    assert!(lev <= levenshtein(a, c) + levenshtein(c, b));
+                    assert!(dl <= damerau_levenshtein(a, c) + damerau_levenshtein(c, b));
+                }
+            }
+        }
+    }
+
+    /// Расстояния до подстрок, заканчивающихся в каждой позиции, по таблице Селлерса.
+    fn sellers(pattern: &str, text: &str) -> Vec<usize> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let mut column: Vec<usize> = (0..=pattern.len()).collect();
+        let mut scores = vec![pattern.len()];
+        for c in text.chars() {
+            let mut next = vec![0; column.len()];
+            for i in 1..column.len() {
+                next[i] = (column[i - 1] + usize::from(pattern[i - 1] != c))
+                    .min(column[i] + 1)
+                    .min(next[i - 1] + 1);
+            }
+            column = next;
+            scores.push(column[pattern.len()]);
+        }
+        scores
+    }
+
+    #[test]
+    fn test_myers() {
+        let mut rng = StdRng::seed_from_u64(0x9abc_def0);
+        for round in 0..300 {
+            // Образцы длиннее слова проверяют перенос приращений между блоками.
+            let len = [0, 1, 3, 8, 63, 64, 65, 70, 127, 128, 129, 200][round % 12];
+            let pattern = word(len, 3, &mut rng);
+            // Текст со вставленным образцом, чтобы вхождения находились и для длинных образцов.
+            let mut text = word(round % 50, 3, &mut rng);
+            if round % 2 == 0 {
+                text.insert_str(text.len() / 2, &pattern);
+            }
+            let max_errors = round % 5 + len / 3;
+
+            let ends: Vec<usize> = (0..=text.len()).collect();
+            let expected: Vec<FuzzyMatch> = sellers(&pattern, &text)
+                .into_iter()
+                .zip(ends)
+                .filter(|&(distance, _)| distance <= max_errors)
+                .map(|(distance, end)| FuzzyMatch { end, distance })
+                .collect();
+            let finder = MyersFinder::new(&pattern, max_errors);
+            let found: Vec<FuzzyMatch> = finder.find_iter(&text).collect();
+            assert_eq!(found, expected, "{pattern}, {text}, {max_errors}");
+        }
+    }
+
+    #[test]
+    fn test_myers_long() {
+        // Лучшее вхождение длинного образца сверяется с расстоянием Левенштейна
+        // до искажённой копии.
+        let mut rng = StdRng::seed_from_u64(0x5eed_0001);
+        for len in [65, 100, 128, 129, 300] {
+            let pattern = word(len, 4, &mut rng);
+            let mut copy: Vec<char> = pattern.chars().collect();
+            copy[len / 3] = 'x';
+            copy.remove(len / 2);
+            copy.insert(len - 10, 'y');
+            let copy: String = copy.into_iter().collect();
+            let errors = levenshtein(&pattern, &copy);
+
+            let text = format!("{}{copy}{}", "z".repeat(20), "z".repeat(20));
+            let best = MyersFinder::new(&pattern, errors)
+                .find_iter(&text)
+                .min_by_key(|found| found.distance)
+                .expect("искажённая копия находится");
+            assert_eq!(best.distance, errors, "len = {len}");
+            assert_eq!(best.end, 20 + copy.len(), "len = {len}");
+        }
+    }
+
+    #[test]
+    fn test_myers_utf8() {
+        let text = "съешь же ещё этих мягких французских булок";
+        let finder = MyersFinder::new("францусских", 1);
+        let found: Vec<&str> = finder
+            .find_iter(text)
+            .map(|found| &text[..found.end])
+            .collect();
+        assert_eq!(found, ["съешь же ещё этих мягких французских"]);
+    }
+
+    #[test]
+    fn test_bk_tree() {
+        let mut rng = StdRng::seed_from_u64(0x0f0f_0f0f);
+        let words: Vec<String> = (0..500).map(|i| word(i % 9 + 1, 4, &mut rng)).collect();
+        let tree: BkTree<String> = words.iter().cloned().collect();
+        let mut unique = words.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(tree.len(), unique.len());
+
+        for query in words.iter().take(50) {
+            for max_distance in 0..4 {
+                let mut expected: Vec<(usize, &String)> = unique
+                    .iter()
+                    .map(|word| (levenshtein(word, query), word))
+                    .filter(|&(distance, _)| distance <= max_distance)
+                    .collect();
+                let mut found = tree.find(query, max_distance);
+                expected.sort();
+                found.sort();
+                assert_eq!(found, expected, "{query}, {max_distance}");
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! BK-дерево (Burkhard–Keller Tree) для поиска похожих слов.
+
+use super::levenshtein;
+
+/// Метрика по умолчанию: расстояние Левенштейна.
+pub type Metric<T> = fn(&T, &T) -> usize;
+
+/// BK-дерево
+/// Принцип работы: каждый узел хранит элемент, а потомков — по расстоянию до него.
+/// Для метрики выполняется неравенство треугольника, поэтому при поиске элементов на
+/// расстоянии не больше `k` от запроса, находящегося на расстоянии `d` от узла, нужно
+/// спускаться только к потомкам с расстоянием от `d - k` до `d + k`.
+/// Когда использовать:
+///  * подсказки «возможно, вы имели в виду» по словарю;
+///  * поиск близких элементов для любой целочисленной метрики (расстояния Левенштейна,
+///    Дамерау — Левенштейна, Хэмминга).
+///
+/// Характеристики:
+///  * Сложность по времени: O(log n) вычислений метрики на вставку в среднем; поиск
+///    с малым `k` просматривает небольшую долю словаря, в худшем случае — весь.
+///  * Сложность по памяти: O(n).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::BkTree;
+///
+/// let tree: BkTree<String> = ["book", "books", "cake", "boo", "cape", "cart"]
+///     .into_iter()
+///     .map(String::from)
+///     .collect();
+/// let found: Vec<(usize, &str)> = tree
+///     .find(&"bo".to_string(), 2)
+///     .into_iter()
+///     .map(|(distance, word)| (distance, word.as_str()))
+///     .collect();
+/// assert_eq!(found, [(1, "boo"), (2, "book")]);
+/// ```
+///
+/// С другой метрикой:
+///
+/// ```
+/// use exa_search::{BkTree, damerau_levenshtein};
+///
+/// let mut tree = BkTree::with_metric(|a: &&str, b: &&str| damerau_levenshtein(a, b));
+/// tree.insert("receive");
+/// tree.insert("deceive");
+/// assert_eq!(tree.closest(&"recieve", 1), Some((1, &"receive")));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BkTree<T, M = Metric<T>> {
+    metric: M,
+    nodes: Vec<Node<T>>,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    item: T,
+    /// Потомки: расстояние до элемента узла и индекс потомка.
+    children: Vec<(usize, usize)>,
+}
+
+impl BkTree<String> {
+    /// Пустое дерево строк с расстоянием Левенштейна.
+    pub fn new() -> Self {
+        Self::with_metric(|a: &String, b: &String| levenshtein(a, b))
+    }
+}
+
+impl Default for BkTree<String> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, M: Fn(&T, &T) -> usize> BkTree<T, M> {
+    /// Пустое дерево с метрикой `metric`. Метрика должна удовлетворять неравенству
+    /// треугольника, иначе поиск может пропускать элементы.
+    pub fn with_metric(metric: M) -> Self {
+        Self {
+            metric,
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Количество элементов.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Пусто ли дерево.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Добавляет элемент. Возвращает `false`, если элемент на нулевом расстоянии уже есть.
+    pub fn insert(&mut self, item: T) -> bool {
+        if self.nodes.is_empty() {
+            self.nodes.push(Node {
+                item,
+                children: Vec::new(),
+            });
+            return true;
+        }
+
+        let mut index = 0;
+        loop {
+            let distance = (self.metric)(&self.nodes[index].item, &item);
+            if distance == 0 {
+                return false;
+            }
+            let child = self.nodes[index]
+                .children
+                .iter()
+                .find(|&&(d, _)| d == distance);
+            match child {
+                Some(&(_, child)) => index = child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes[index].children.push((distance, child));
+                    self.nodes.push(Node {
+                        item,
+                        children: Vec::new(),
+                    });
+                    return true;
+                }
+            }
+        }
+    }
+
+    /// Все элементы на расстоянии не больше `max_distance` от `query` вместе с
+    /// расстояниями, по возрастанию расстояния.
+    pub fn find(&self, query: &T, max_distance: usize) -> Vec<(usize, &T)> {
+        let mut found = Vec::new();
+        let mut stack: Vec<usize> = if self.nodes.is_empty() {
+            Vec::new()
+        } else {
+            vec![0]
+        };
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let distance = (self.metric)(&node.item, query);
+            if distance <= max_distance {
+                found.push((distance, index));
+            }
+            let range = distance.saturating_sub(max_distance)..=distance + max_distance;
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|(d, _)| range.contains(d))
+                    .map(|&(_, child)| child),
+            );
+        }
+        // Индекс узла — порядок добавления.
+        found.sort_unstable();
+        found
+            .into_iter()
+            .map(|(distance, index)| (distance, &self.nodes[index].item))
+            .collect()
+    }
+
+    /// Ближайший к `query` элемент на расстоянии не больше `max_distance`.
+    /// Из равноудалённых возвращается добавленный раньше.
+    pub fn closest(&self, query: &T, max_distance: usize) -> Option<(usize, &T)> {
+        self.find(query, max_distance).into_iter().next()
+    }
+
+    /// Элементы в порядке добавления.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.nodes.iter().map(|node| &node.item)
+    }
+}
+
+impl<T, M: Fn(&T, &T) -> usize> Extend<T> for BkTree<T, M> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+impl FromIterator<String> for BkTree<String> {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        tree.extend(iter);
+        tree
+    }
+}
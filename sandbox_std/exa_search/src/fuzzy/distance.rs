@@ -0,0 +1,187 @@
+//! Редакционные расстояния (Edit Distance).
+//!
+//! Строковые функции сравнивают символы (`char`), а не байты, варианты `_slice` —
+//! элементы произвольных срезов.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Расстояние Левенштейна (Levenshtein Distance)
+/// Наименьшее количество вставок, удалений и замен символов, превращающих `a` в `b`.
+///
+/// Характеристики:
+///  * Сложность по времени: O(nm).
+///  * Сложность по памяти: O(min(n, m)).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::levenshtein;
+///
+/// assert_eq!(levenshtein("kitten", "sitting"), 3);
+/// assert_eq!(levenshtein("ёжик", "ежик"), 1);
+/// assert_eq!(levenshtein("", "abc"), 3);
+/// ```
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    levenshtein_slice(&a, &b)
+}
+
+/// Расстояние Левенштейна между срезами.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::levenshtein_slice;
+///
+/// assert_eq!(levenshtein_slice(&[1, 2, 3], &[1, 3]), 1);
+/// ```
+pub fn levenshtein_slice<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    // Строка таблицы по более короткому срезу.
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, x) in a.iter().enumerate() {
+        // `diagonal` — значение над текущей клеткой слева до её перезаписи.
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (diagonal + usize::from(x != y))
+                .min(above + 1)
+                .min(row[j] + 1);
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Расстояние оптимального выравнивания строк (Optimal String Alignment)
+/// Расстояние Левенштейна, в котором перестановка двух соседних символов считается
+/// одной операцией. Каждую подстроку можно изменить не более одного раза, поэтому
+/// `"CA"` → `"ABC"` стоит 3 операции, а не 2, как в [`damerau_levenshtein`], и
+/// неравенство треугольника может нарушаться.
+///
+/// Характеристики:
+///  * Сложность по времени: O(nm).
+///  * Сложность по памяти: O(min(n, m)).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::osa_distance;
+///
+/// assert_eq!(osa_distance("recieve", "receive"), 1);
+/// assert_eq!(osa_distance("CA", "ABC"), 3);
+/// ```
+pub fn osa_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    osa_distance_slice(&a, &b)
+}
+
+/// Расстояние оптимального выравнивания между срезами.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::osa_distance_slice;
+///
+/// assert_eq!(osa_distance_slice(&[1, 2, 3], &[2, 1, 3]), 1);
+/// ```
+pub fn osa_distance_slice<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    // Три последние строки таблицы: перестановка смотрит на две строки назад.
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let mut cost = (previous[j - 1] + usize::from(a[i - 1] != b[j - 1]))
+                .min(previous[j] + 1)
+                .min(current[j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cost = cost.min(before[j - 2] + 1);
+            }
+            current[j] = cost;
+        }
+        (before, previous, current) = (previous, current, before);
+    }
+    previous[b.len()]
+}
+
+/// Расстояние Дамерау — Левенштейна (Damerau–Levenshtein Distance)
+/// Наименьшее количество вставок, удалений, замен и перестановок соседних символов,
+/// превращающих `a` в `b`. В отличие от [`osa_distance`], между переставленными
+/// символами можно вставлять новые, поэтому это настоящая метрика.
+///
+/// Характеристики:
+///  * Сложность по времени: O(nm).
+///  * Сложность по памяти: O(nm).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::damerau_levenshtein;
+///
+/// assert_eq!(damerau_levenshtein("recieve", "receive"), 1);
+/// assert_eq!(damerau_levenshtein("CA", "ABC"), 2);
+/// ```
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    damerau_levenshtein_slice(&a, &b)
+}
+
+/// Расстояние Дамерау — Левенштейна между срезами.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::damerau_levenshtein_slice;
+///
+/// assert_eq!(damerau_levenshtein_slice(&[3, 1], &[1, 2, 3]), 2);
+/// ```
+pub fn damerau_levenshtein_slice<T: Eq + Hash>(a: &[T], b: &[T]) -> usize {
+    let (m, n) = (a.len(), b.len());
+    let width = n + 2;
+    let infinity = m + n;
+    // Таблица со сдвигом на единицу: строка и столбец 0 заполнены «бесконечностью».
+    let mut table = vec![0; (m + 2) * width];
+    table[0] = infinity;
+    for i in 0..=m {
+        table[(i + 1) * width] = infinity;
+        table[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=n {
+        table[j + 1] = infinity;
+        table[width + j + 1] = j;
+    }
+
+    // Последняя строка `a`, в которой встречался символ.
+    let mut last_row: HashMap<&T, usize> = HashMap::new();
+    for i in 1..=m {
+        // Последний столбец текущей строки, где символы совпали.
+        let mut last_column = 0;
+        for j in 1..=n {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_column;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_column = j;
+                0
+            } else {
+                1
+            };
+            // Перестановка `a[k - 1]` и `b[l - 1]` с удалением и вставкой между ними.
+            let transposition = table[k * width + l] + (i - k - 1) + 1 + (j - l - 1);
+            table[(i + 1) * width + j + 1] = (table[i * width + j] + cost)
+                .min(table[(i + 1) * width + j] + 1)
+                .min(table[i * width + j + 1] + 1)
+                .min(transposition);
+        }
+        last_row.insert(&a[i - 1], i);
+    }
+    table[(m + 1) * width + n + 1]
+}
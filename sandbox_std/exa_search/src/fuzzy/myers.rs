@@ -0,0 +1,185 @@
+//! Приближённый поиск подстроки битово-параллельным алгоритмом Майерса (Myers).
+
+use std::{collections::HashMap, str::CharIndices};
+
+/// Количество строк таблицы в одном блоке битовых векторов.
+const WORD: usize = u64::BITS as usize;
+
+/// Приближённое вхождение образца.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FuzzyMatch {
+    /// Позиция (в байтах) за последним символом вхождения.
+    pub end: usize,
+    /// Наименьшее расстояние Левенштейна между образцом и подстрокой текста,
+    /// заканчивающейся в `end`.
+    pub distance: usize,
+}
+
+/// Приближённый поиск подстроки алгоритмом Майерса
+/// Принцип работы: для каждой позиции текста нужно наименьшее расстояние Левенштейна
+/// от образца до подстроки, которая там заканчивается, — последняя клетка столбца
+/// таблицы динамического программирования (алгоритм Селлерса). Соседние клетки столбца
+/// отличаются не больше чем на единицу, поэтому столбец хранится как два битовых
+/// вектора приращений и пересчитывается десятком битовых операций над словом.
+/// Образцы длиннее 64 символов делятся на блоки по 64 строки (вариант Хююрё, Hyyrö):
+/// блоки пересчитываются сверху вниз, и каждый передаёт следующему приращение
+/// своей нижней строки.
+/// Когда использовать:
+///  * поиск в тексте с опечатками: найти «recieve» в «... receive ...»;
+///  * короткие образцы и небольшое допустимое число ошибок.
+///
+/// Характеристики:
+///  * Сложность по времени: O(m) на подготовку, O(n⌈m/64⌉) на поиск.
+///  * Сложность по памяти: O(σ⌈m/64⌉), где σ — количество различных символов образца.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::MyersFinder;
+///
+/// let finder = MyersFinder::new("receive", 1);
+/// let found: Vec<_> = finder.find_iter("we recelve mail").collect();
+/// assert_eq!(found.iter().map(|m| (m.end, m.distance)).collect::<Vec<_>>(), [(10, 1)]);
+///
+/// // Перестановка соседних букв — две ошибки.
+/// let finder = MyersFinder::new("receive", 2);
+/// let ends: Vec<usize> = finder.find_iter("we recieve mail").map(|m| m.end).collect();
+/// assert_eq!(ends, [8, 10]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MyersFinder {
+    pattern: Vec<char>,
+    max_errors: usize,
+    /// Битовые маски позиций каждого символа в образце, по блоку на 64 символа.
+    masks: HashMap<char, Vec<u64>>,
+}
+
+impl MyersFinder {
+    /// Подготавливает образец, допуская не больше `max_errors` ошибок.
+    pub fn new(pattern: &str, max_errors: usize) -> Self {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let blocks = pattern.len().div_ceil(WORD);
+        let mut masks = HashMap::new();
+        for (i, &c) in pattern.iter().enumerate() {
+            masks.entry(c).or_insert_with(|| vec![0; blocks])[i / WORD] |= 1 << (i % WORD);
+        }
+        Self {
+            pattern,
+            max_errors,
+            masks,
+        }
+    }
+
+    /// Допустимое число ошибок.
+    pub fn max_errors(&self) -> usize {
+        self.max_errors
+    }
+
+    /// Итератор по концам подстрок `text`, отличающихся от образца не больше чем на
+    /// `max_errors` операций, по возрастанию. Позиция 0 сообщается, если образец
+    /// достаточно короток, чтобы совпасть с пустой строкой.
+    pub fn find_iter<'a>(&'a self, text: &'a str) -> MyersMatches<'a> {
+        let len = self.pattern.len();
+        let blocks = len.div_ceil(WORD);
+        // Первый столбец — 0, 1, ..., m: все вертикальные приращения равны +1.
+        MyersMatches {
+            finder: self,
+            chars: text.char_indices(),
+            positive: vec![u64::MAX; blocks],
+            negative: vec![0; blocks],
+            score: len,
+            start: true,
+        }
+    }
+}
+
+/// Итератор по приближённым вхождениям, см. [`MyersFinder::find_iter`].
+#[derive(Debug, Clone)]
+pub struct MyersMatches<'a> {
+    finder: &'a MyersFinder,
+    chars: CharIndices<'a>,
+    /// Векторы положительных и отрицательных вертикальных приращений столбца по блокам.
+    positive: Vec<u64>,
+    negative: Vec<u64>,
+    /// Значение последней клетки текущего столбца.
+    score: usize,
+    /// Позиция 0 ещё не проверена.
+    start: bool,
+}
+
+impl MyersMatches<'_> {
+    /// Пересчитывает столбец для очередного символа текста.
+    fn step(&mut self, c: char) {
+        let Some(last) = self.finder.pattern.len().checked_sub(1) else {
+            return;
+        };
+        let masks = self.finder.masks.get(&c);
+        // Верхняя строка таблицы нулевая: вхождение может начаться где угодно,
+        // поэтому в первый блок приращение не вдвигается.
+        let mut carry = 0;
+        for (block, (positive, negative)) in
+            self.positive.iter_mut().zip(&mut self.negative).enumerate()
+        {
+            // Строка, приращение которой передаётся дальше: нижняя строка блока
+            // или последняя строка образца.
+            let high = if block == last / WORD {
+                1 << (last % WORD)
+            } else {
+                1 << (WORD - 1)
+            };
+            let mut equal = masks.map_or(0, |masks| masks[block]);
+
+            let vertical = equal | *negative;
+            // Отрицательное приращение сверху действует как совпадение в первой строке блока.
+            if carry < 0 {
+                equal |= 1;
+            }
+            let horizontal = ((equal & *positive).wrapping_add(*positive) ^ *positive) | equal;
+            let mut up = *negative | !(horizontal | *positive);
+            let mut down = *positive & horizontal;
+            let carry_in = carry;
+            carry = if up & high != 0 {
+                1
+            } else if down & high != 0 {
+                -1
+            } else {
+                0
+            };
+            up <<= 1;
+            down <<= 1;
+            if carry_in < 0 {
+                down |= 1;
+            } else if carry_in > 0 {
+                up |= 1;
+            }
+            *positive = down | !(vertical | up);
+            *negative = up & vertical;
+        }
+        // Приращение последней строки образца меняет расстояние.
+        self.score = self.score.wrapping_add_signed(carry);
+    }
+}
+
+impl Iterator for MyersMatches<'_> {
+    type Item = FuzzyMatch;
+
+    fn next(&mut self) -> Option<FuzzyMatch> {
+        let max_errors = self.finder.max_errors;
+        if std::mem::take(&mut self.start) && self.score <= max_errors {
+            return Some(FuzzyMatch {
+                end: 0,
+                distance: self.score,
+            });
+        }
+        while let Some((index, c)) = self.chars.next() {
+            self.step(c);
+            if self.score <= max_errors {
+                return Some(FuzzyMatch {
+                    end: index + c.len_utf8(),
+                    distance: self.score,
+                });
+            }
+        }
+        None
+    }
+}
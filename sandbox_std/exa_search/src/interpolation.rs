@@ -0,0 +1,148 @@
+//! Интерполяционный поиск (Interpolation Search).
+
+use std::cmp::Ordering;
+
+/// Целочисленный ключ, для которого можно вычислить расстояние между значениями.
+pub trait NumericKey: Ord + Copy {
+    /// Расстояние `self - lower`, `lower <= self`.
+    fn distance(self, lower: Self) -> u128;
+}
+
+macro_rules! impl_numeric_key {
+    ($($t:ty),*) => {
+        $(
+            impl NumericKey for $t {
+                fn distance(self, lower: Self) -> u128 {
+                    (self as i128 - lower as i128) as u128
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_key!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Интерполяционный поиск (Interpolation Search)
+/// Принцип работы: следующая позиция выбирается не посередине, а пропорционально
+/// значению: если искомое на 30% пути от `arr[left]` до `arr[right]`, проверяется элемент
+/// на 30% диапазона. Если шаг отбросил меньше половины диапазона, следом выполняется
+/// шаг бинарного поиска, поэтому на неравномерных данных поиск не хуже O(log n).
+/// Когда использовать:
+///  * массив отсортирован, а значения распределены почти равномерно (идентификаторы,
+///    временные метки).
+///
+/// Характеристики:
+///  * Сложность по времени: O(log log n) на равномерных данных, O(log n) в худшем случае.
+///  * Сложность по памяти: O(1).
+///  * Требование: массив должен быть отсортирован.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::interpolation_search;
+///
+/// let arr = [1, 2, 3, 4, 5];
+/// assert_eq!(interpolation_search(&arr, &3), Some(2));
+/// ```
+///
+/// ```
+/// use exa_search::interpolation_search;
+///
+/// let arr: Vec<u64> = (0..1000).map(|i| i * 10).collect();
+/// assert_eq!(interpolation_search(&arr, &5000), Some(500));
+/// assert_eq!(interpolation_search(&arr, &5001), None);
+/// ```
+///
+/// Неравномерные данные:
+///
+/// ```
+/// use exa_search::interpolation_search;
+///
+/// let arr: Vec<u64> = (0..64).map(|i| 1 << i).collect();
+/// assert_eq!(interpolation_search(&arr, &(1 << 40)), Some(40));
+/// ```
+pub fn interpolation_search<T: NumericKey>(arr: &[T], target: &T) -> Option<usize> {
+    let target = *target;
+    // Искомое может быть только в `arr[left..=right]`.
+    let (mut left, mut right) = (0, arr.len().checked_sub(1)?);
+
+    while left <= right && arr[left] <= target && target <= arr[right] {
+        // Все элементы диапазона равны, если `span == 0`: проверяем левый.
+        let span = arr[right].distance(arr[left]);
+        let offset = (target.distance(arr[left]) * (right - left) as u128).checked_div(span);
+        let probe = left + offset.unwrap_or(0) as usize;
+
+        let width = right - left;
+        match arr[probe].cmp(&target) {
+            Ordering::Equal => return Some(probe),
+            Ordering::Less => left = probe + 1,
+            Ordering::Greater => right = probe.checked_sub(1)?,
+        }
+
+        // Интерполяция плохо сократила диапазон: шаг бинарного поиска.
+        if left <= right && right - left > width / 2 {
+            let mid = left + (right - left) / 2;
+            match arr[mid].cmp(&target) {
+                Ordering::Equal => return Some(mid),
+                Ordering::Less => left = mid + 1,
+                Ordering::Greater => right = mid.checked_sub(1)?,
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exponential_search, exponential_search_unbounded};
+
+    fn check(arr: &[i64]) {
+        let (min, max) = (arr.first().copied(), arr.last().copied());
+        let targets = arr
+            .iter()
+            .flat_map(|&x| [x.saturating_sub(1), x, x.saturating_add(1)])
+            .chain(min.map(|_| i64::MIN))
+            .chain(max.map(|_| i64::MAX));
+        for target in targets {
+            let expected = arr.contains(&target);
+            for (name, found) in [
+                ("interpolation", interpolation_search(arr, &target)),
+                ("exponential", exponential_search(arr, &target)),
+                (
+                    "unbounded",
+                    exponential_search_unbounded(|i| arr.get(i).copied(), &target),
+                ),
+            ] {
+                assert_eq!(found.is_some(), expected, "{name}, {target}, {arr:?}");
+                if let Some(index) = found {
+                    assert_eq!(arr[index], target, "{name}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_distributions() {
+        check(&[]);
+        check(&[5]);
+        check(&[i64::MIN, 0, i64::MAX]);
+        check(&(0..200).map(|i| i * 7 - 300).collect::<Vec<_>>());
+        check(&(0..200).map(|i| i / 10).collect::<Vec<_>>());
+        check(&(0..62).map(|i| 1 << i).collect::<Vec<_>>());
+        check(&(0..200).map(|i: i64| i.pow(3)).collect::<Vec<_>>());
+        check(&[0; 50]);
+    }
+
+    #[test]
+    fn test_skewed_probes() {
+        // Экспоненциальный рост: чистая интерполяция делала бы O(n) шагов.
+        let arr: Vec<u64> = (0..10_000u64).map(|i| i * i * i).collect();
+        for target in [1, 27, 8_000_000_000] {
+            assert_eq!(
+                interpolation_search(&arr, &target),
+                arr.binary_search(&target).ok()
+            );
+        }
+    }
+}
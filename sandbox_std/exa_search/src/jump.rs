@@ -0,0 +1,56 @@
+//! Поиск прыжками (Jump Search).
+
+use std::cmp::Ordering;
+
+/// Поиск прыжками (Jump Search)
+/// Принцип работы: массив просматривается блоками длиной `√n`, пока последний элемент
+/// блока меньше искомого, затем нужный блок просматривается линейно.
+/// Когда использовать:
+///  * массив отсортирован;
+///  * переход назад дорог (например, данные читаются последовательно).
+///
+/// Характеристики:
+///  * Сложность по времени: O(√n).
+///  * Сложность по памяти: O(1).
+///  * Требование: массив должен быть отсортирован.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::jump_search;
+///
+/// let arr = [1, 2, 3, 4, 5];
+/// assert_eq!(jump_search(&arr, &3), Some(2));
+/// ```
+///
+/// ```
+/// use exa_search::jump_search;
+///
+/// let arr = [10, 20, 30, 40, 50, 60, 70, 80, 90];
+/// assert_eq!(jump_search(&arr, &90), Some(8));
+/// assert_eq!(jump_search(&arr, &35), None);
+/// ```
+pub fn jump_search<T: Ord>(arr: &[T], target: &T) -> Option<usize> {
+    let len = arr.len();
+    let step = len.isqrt().max(1);
+
+    // Начало блока, последний элемент которого не меньше искомого.
+    let mut start = 0;
+    while start < len && arr[(start + step).min(len) - 1] < *target {
+        start += step;
+    }
+
+    for (index, element) in arr
+        .iter()
+        .enumerate()
+        .take((start + step).min(len))
+        .skip(start)
+    {
+        match element.cmp(target) {
+            Ordering::Equal => return Some(index),
+            Ordering::Greater => return None,
+            Ordering::Less => {}
+        }
+    }
+    None
+}
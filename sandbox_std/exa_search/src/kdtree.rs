@@ -0,0 +1,308 @@
+//! K-мерное дерево (k-d Tree) для поиска ближайших точек.
+
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// Найденная точка.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Neighbor {
+    /// Индекс точки в срезе, по которому построено дерево.
+    pub index: usize,
+    /// Евклидово расстояние до точки запроса.
+    pub distance: f64,
+}
+
+/// K-мерное дерево (k-d Tree)
+/// Принцип работы: точки рекурсивно делятся пополам медианой по одной координате,
+/// координаты чередуются с глубиной. Каждое поддерево занимает полупространство, поэтому
+/// при поиске ближайших соседей поддерево по другую сторону от разделяющей плоскости
+/// просматривается, только если плоскость ближе найденного кандидата.
+/// Дерево хранится неявно в одном массиве: корень поддерева — средний элемент его
+/// отрезка, левое и правое поддеревья — половины отрезка слева и справа от него.
+/// Когда использовать:
+///  * точки в пространстве небольшой размерности (2–10) и много запросов к ним:
+///    ближайший объект на карте, поиск похожих векторов признаков.
+///
+/// Характеристики:
+///  * Сложность построения: O(n log n).
+///  * Поиск ближайшего соседа: O(log n) в среднем для равномерно разбросанных точек,
+///    O(n) в худшем случае; с ростом размерности приближается к полному перебору.
+///  * Поиск в прямоугольнике: O(n^(1 - 1/D) + k), где k — количество найденных точек.
+///  * Сложность по памяти: O(n).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::KdTree;
+///
+/// let cities = [[55.75, 37.62], [59.94, 30.31], [56.84, 60.61], [54.99, 73.37]];
+/// let tree = KdTree::new(&cities);
+///
+/// let nearest = tree.nearest(&[56.33, 44.0]).unwrap();
+/// assert_eq!(nearest.index, 0);
+///
+/// let two: Vec<usize> = tree.k_nearest(&[56.0, 66.0], 2).iter().map(|n| n.index).collect();
+/// assert_eq!(two, [2, 3]);
+///
+/// assert_eq!(tree.range(&[55.0, 30.0], &[60.0, 40.0]), [0, 1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KdTree<const D: usize> {
+    /// Точки в порядке неявного дерева вместе с исходными индексами.
+    nodes: Vec<([f64; D], usize)>,
+}
+
+impl<const D: usize> KdTree<D> {
+    /// Строит дерево по точкам за O(n log n).
+    ///
+    /// # Паника
+    ///
+    /// Если какая-то координата — NaN.
+    pub fn new(points: &[[f64; D]]) -> Self {
+        const {
+            assert!(D > 0, "размерность должна быть положительной")
+        };
+        assert!(
+            points.iter().flatten().all(|x| !x.is_nan()),
+            "координаты не должны быть NaN"
+        );
+        let mut nodes: Vec<([f64; D], usize)> = points.iter().copied().zip(0..).collect();
+        build(&mut nodes, 0);
+        Self { nodes }
+    }
+
+    /// Количество точек.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Пусто ли дерево.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Ближайшая к `query` точка или `None`, если дерево пусто.
+    pub fn nearest(&self, query: &[f64; D]) -> Option<Neighbor> {
+        self.k_nearest(query, 1).pop()
+    }
+
+    /// `k` ближайших к `query` точек по возрастанию расстояния (все точки, если их
+    /// меньше `k`).
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// use exa_search::KdTree;
+    ///
+    /// let points: Vec<[f64; 1]> = (0..10).map(|x| [x as f64]).collect();
+    /// let tree = KdTree::new(&points);
+    /// let found: Vec<f64> = tree.k_nearest(&[4.2], 3).iter().map(|n| n.distance).collect();
+    /// assert_eq!(found.len(), 3);
+    /// assert!((found[2] - 1.2).abs() < 1e-9);
+    /// ```
+    pub fn k_nearest(&self, query: &[f64; D], k: usize) -> Vec<Neighbor> {
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            k_nearest(&self.nodes, 0, query, k, &mut best);
+        }
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|Candidate { distance, index }| Neighbor {
+                index,
+                distance: distance.sqrt(),
+            })
+            .collect()
+    }
+
+    /// Индексы точек внутри прямоугольника `min..=max` (по всем координатам), по
+    /// возрастанию.
+    pub fn range(&self, min: &[f64; D], max: &[f64; D]) -> Vec<usize> {
+        let mut found = Vec::new();
+        range(&self.nodes, 0, min, max, &mut found);
+        found.sort_unstable();
+        found
+    }
+}
+
+/// Упорядочивает отрезок в неявное дерево: медиана по координате `depth % D` в середину.
+fn build<const D: usize>(nodes: &mut [([f64; D], usize)], depth: usize) {
+    if nodes.len() <= 1 {
+        return;
+    }
+    let axis = depth % D;
+    let mid = nodes.len() / 2;
+    nodes.select_nth_unstable_by(mid, |a, b| a.0[axis].total_cmp(&b.0[axis]));
+    let (left, right) = nodes.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+/// Квадрат евклидова расстояния.
+fn squared_distance<const D: usize>(a: &[f64; D], b: &[f64; D]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+/// Кандидат в ближайшие соседи. Куча упорядочена по убыванию расстояния: на вершине —
+/// самый дальний из лучших.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    /// Квадрат расстояния.
+    distance: f64,
+    index: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+fn k_nearest<const D: usize>(
+    nodes: &[([f64; D], usize)],
+    depth: usize,
+    query: &[f64; D],
+    k: usize,
+    best: &mut BinaryHeap<Candidate>,
+) {
+    if nodes.is_empty() {
+        return;
+    }
+    let mid = nodes.len() / 2;
+    let (point, index) = &nodes[mid];
+    best.push(Candidate {
+        distance: squared_distance(point, query),
+        index: *index,
+    });
+    if best.len() > k {
+        best.pop();
+    }
+
+    let axis = depth % D;
+    let offset = query[axis] - point[axis];
+    let (left, right) = (&nodes[..mid], &nodes[mid + 1..]);
+    let (near, far) = if offset < 0.0 {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    k_nearest(near, depth + 1, query, k, best);
+    // Дальнее полупространство может содержать точку ближе худшего кандидата, только
+    // если ближе него разделяющая плоскость.
+    let worst = best.peek().map_or(f64::INFINITY, |worst| worst.distance);
+    if best.len() < k || offset * offset < worst {
+        k_nearest(far, depth + 1, query, k, best);
+    }
+}
+
+fn range<const D: usize>(
+    nodes: &[([f64; D], usize)],
+    depth: usize,
+    min: &[f64; D],
+    max: &[f64; D],
+    found: &mut Vec<usize>,
+) {
+    if nodes.is_empty() {
+        return;
+    }
+    let mid = nodes.len() / 2;
+    let (point, index) = &nodes[mid];
+    if (0..D).all(|axis| min[axis] <= point[axis] && point[axis] <= max[axis]) {
+        found.push(*index);
+    }
+
+    let axis = depth % D;
+    if min[axis] <= point[axis] {
+        range(&nodes[..mid], depth + 1, min, max, found);
+    }
+    if point[axis] <= max[axis] {
+        range(&nodes[mid + 1..], depth + 1, min, max, found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    /// Псевдослучайные точки на сетке `0..side` (с повторами и совпадающими координатами).
+    fn random_points<const D: usize>(len: usize, side: u32, rng: &mut StdRng) -> Vec<[f64; D]> {
+        (0..len)
+            .map(|_| std::array::from_fn(|_| f64::from(rng.random_range(0..2 * side)) * 0.5))
+            .collect()
+    }
+
+    /// Расстояния до `k` ближайших точек перебором.
+    fn brute_k_nearest<const D: usize>(
+        points: &[[f64; D]],
+        query: &[f64; D],
+        k: usize,
+    ) -> Vec<f64> {
+        let mut distances: Vec<f64> = points
+            .iter()
+            .map(|point| squared_distance(point, query).sqrt())
+            .collect();
+        distances.sort_by(f64::total_cmp);
+        distances.truncate(k);
+        distances
+    }
+
+    fn check<const D: usize>(rng: &mut StdRng) {
+        for len in [0, 1, 2, 7, 100, 500] {
+            let points = random_points::<D>(len, 20, rng);
+            let tree = KdTree::new(&points);
+            assert_eq!(tree.len(), len);
+
+            for query in random_points::<D>(20, 24, rng) {
+                for k in [1, 3, 10, len + 1] {
+                    let found = tree.k_nearest(&query, k);
+                    let distances: Vec<f64> = found.iter().map(|n| n.distance).collect();
+                    assert_eq!(distances, brute_k_nearest(&points, &query, k));
+                    for neighbor in &found {
+                        let distance = squared_distance(&points[neighbor.index], &query).sqrt();
+                        assert_eq!(distance, neighbor.distance);
+                    }
+                }
+                let nearest = tree.nearest(&query).map(|n| n.distance);
+                assert_eq!(nearest, brute_k_nearest(&points, &query, 1).pop());
+
+                let min = query.map(|x| x - 3.0);
+                let max = query.map(|x| x + 2.0);
+                let expected: Vec<usize> = (0..len)
+                    .filter(|&i| (0..D).all(|a| min[a] <= points[i][a] && points[i][a] <= max[a]))
+                    .collect();
+                assert_eq!(tree.range(&min, &max), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_brute_force() {
+        let mut rng = StdRng::seed_from_u64(0xdead_beef);
+        check::<1>(&mut rng);
+        check::<2>(&mut rng);
+        check::<3>(&mut rng);
+        check::<5>(&mut rng);
+    }
+
+    #[test]
+    #[should_panic(expected = "NaN")]
+    fn test_nan() {
+        KdTree::new(&[[0.0, f64::NAN]]);
+    }
+}
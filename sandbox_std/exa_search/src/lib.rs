@@ -3,6 +3,57 @@
 //! протестированы на скорость исполнения и реализованы на языке Rust.
 //!
 //! @todo сравнить со стандартным в rust
+//!
+//! Все стратегии доступны через общий трейт [`Searcher`], см. [`searchers`].
+//! Поиск подстроки в тексте и байтовых буферах — в модуле [`substring`], поиск с
+//! опечатками — в модуле [`fuzzy`].
+
+pub mod bounds;
+pub mod branchless;
+pub mod exponential;
+pub mod eytzinger;
+pub mod fibonacci;
+pub mod fuzzy;
+pub mod interpolation;
+pub mod jump;
+pub mod kdtree;
+pub mod matrix;
+pub mod searcher;
+pub mod sets;
+pub mod substring;
+pub mod ternary;
+
+pub use bounds::{
+    equal_range, equal_range_by, equal_range_by_key, lower_bound, lower_bound_by,
+    lower_bound_by_key, partition_point, upper_bound, upper_bound_by, upper_bound_by_key,
+};
+pub use branchless::{branchless_binary_search, branchless_lower_bound};
+pub use exponential::{exponential_lower_bound, exponential_search, exponential_search_unbounded};
+pub use eytzinger::EytzingerIndex;
+pub use fibonacci::fibonacci_search;
+pub use fuzzy::{
+    BkTree, FuzzyMatch, MyersFinder, damerau_levenshtein, damerau_levenshtein_slice, levenshtein,
+    levenshtein_slice, osa_distance, osa_distance_slice,
+};
+pub use interpolation::{NumericKey, interpolation_search};
+pub use jump::jump_search;
+pub use kdtree::{KdTree, Neighbor};
+pub use matrix::staircase_search;
+pub use searcher::{
+    BinarySearch, BranchlessSearch, ExponentialSearch, FibonacciSearch, InterpolationSearch,
+    JumpSearch, LinearSearch, Searcher, TernarySearch, searchers,
+};
+pub use sets::{
+    AdaptiveIntersection, Difference, Intersection, KMerge, Union, difference, intersect_adaptive,
+    intersect_galloping, kmerge, union,
+};
+pub use substring::{
+    AhoCorasick, AhoCorasickOptions, HorspoolFinder, HorspoolMatches, KmpFinder, KmpMatches,
+    MatchKind, RabinKarpFinder, RabinKarpMatches, ZFinder, ZMatches, horspool::horspool_find_iter,
+    kmp::kmp_find_iter, rabin_karp::rabin_karp_find_iter, z_algorithm::z_find_iter,
+    z_algorithm::z_function,
+};
+pub use ternary::ternary_search;
 
 /// Линейный поиск (Linear Search)
 /// Характеристики:
@@ -0,0 +1,111 @@
+//! Поиск в матрице, отсортированной по строкам и столбцам.
+
+use std::cmp::Ordering;
+
+/// Лестничный поиск (Staircase Search)
+/// Принцип работы: поиск начинается в правом верхнем углу. Если элемент больше
+/// искомого, искомого нет во всём столбце ниже — сдвигаемся влево; если меньше, его нет
+/// во всей строке левее — сдвигаемся вниз. Каждый шаг отбрасывает строку или столбец,
+/// путь похож на лестницу.
+/// Когда использовать:
+///  * матрица отсортирована по возрастанию и в каждой строке, и в каждом столбце
+///    (таблицы, где значение растёт по обеим осям), но не целиком.
+///
+/// Характеристики:
+///  * Сложность по времени: O(r + c), где r и c — количество строк и столбцов.
+///  * Сложность по памяти: O(1).
+///  * Требование: все строки одной длины.
+///
+/// Возвращает `(строка, столбец)` одного из элементов, равных `target`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::staircase_search;
+///
+/// let matrix = [
+///     [1, 4, 7, 11],
+///     [2, 5, 8, 12],
+///     [3, 6, 9, 16],
+///     [10, 13, 14, 17],
+/// ];
+/// assert_eq!(staircase_search(&matrix, &9), Some((2, 2)));
+/// assert_eq!(staircase_search(&matrix, &10), Some((3, 0)));
+/// assert_eq!(staircase_search(&matrix, &15), None);
+/// ```
+///
+/// ```
+/// use exa_search::staircase_search;
+///
+/// let matrix = vec![vec!["a", "c"], vec!["b", "d"]];
+/// assert_eq!(staircase_search(&matrix, &"d"), Some((1, 1)));
+/// ```
+///
+/// # Паника
+///
+/// Если строки разной длины.
+pub fn staircase_search<T, R>(matrix: &[R], target: &T) -> Option<(usize, usize)>
+where
+    T: Ord,
+    R: AsRef<[T]>,
+{
+    let columns = matrix.first()?.as_ref().len();
+    assert!(
+        matrix.iter().all(|row| row.as_ref().len() == columns),
+        "строки матрицы должны быть одной длины"
+    );
+
+    let (mut row, mut column) = (0, columns.checked_sub(1)?);
+    while row < matrix.len() {
+        match matrix[row].as_ref()[column].cmp(target) {
+            Ordering::Equal => return Some((row, column)),
+            Ordering::Greater => column = column.checked_sub(1)?,
+            Ordering::Less => row += 1,
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    /// Матрица `rows × columns`, отсортированная по строкам и столбцам, с повторами.
+    fn sorted_matrix(rows: usize, columns: usize, rng: &mut StdRng) -> Vec<Vec<u32>> {
+        let mut matrix = vec![vec![0; columns]; rows];
+        for i in 0..rows {
+            for j in 0..columns {
+                let above = if i > 0 { matrix[i - 1][j] } else { 0 };
+                let left = if j > 0 { matrix[i][j - 1] } else { 0 };
+                matrix[i][j] = above.max(left) + rng.random_range(0..3);
+            }
+        }
+        matrix
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(0x7777_1111);
+        for rows in 0..8 {
+            for columns in 0..8 {
+                let matrix = sorted_matrix(rows, columns, &mut rng);
+                let max = matrix.iter().flatten().max().copied().unwrap_or(0);
+                for target in 0..max + 2 {
+                    let expected = matrix.iter().flatten().any(|&x| x == target);
+                    match staircase_search(&matrix, &target) {
+                        Some((i, j)) => assert_eq!(matrix[i][j], target),
+                        None => assert!(!expected, "{matrix:?}, {target}"),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "одной длины")]
+    fn test_ragged() {
+        staircase_search(&[vec![1, 2], vec![3]], &3);
+    }
+}
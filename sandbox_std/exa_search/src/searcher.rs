@@ -0,0 +1,166 @@
+//! Общий интерфейс стратегий поиска.
+//!
+//! Каждая стратегия — отдельный тип без полей, реализующий [`Searcher`]. Стратегию
+//! можно выбрать параметром типа или перебрать все через `&dyn Searcher<T>`.
+
+use crate::{
+    NumericKey, binary_search, branchless_binary_search, exponential_search, fibonacci_search,
+    interpolation_search, jump_search, linear_search, ternary_search,
+};
+
+/// Стратегия поиска элемента в отсортированном срезе.
+///
+/// # Примеры
+///
+/// Выбор стратегии параметром типа:
+///
+/// ```
+/// use exa_search::{BinarySearch, JumpSearch, Searcher};
+///
+/// fn contains<S: Searcher<u32>>(searcher: S, arr: &[u32], target: u32) -> bool {
+///     searcher.search(arr, &target).is_some()
+/// }
+///
+/// let arr = [1, 3, 5, 7, 9];
+/// assert!(contains(BinarySearch, &arr, 7));
+/// assert!(!contains(JumpSearch, &arr, 4));
+/// ```
+///
+/// Перебор всех стратегий:
+///
+/// ```
+/// use exa_search::{Searcher, searchers};
+///
+/// let arr = [10, 20, 30, 40, 50];
+/// for searcher in searchers::<i32>() {
+///     assert_eq!(searcher.search(&arr, &40), Some(3), "{}", searcher.name());
+///     assert_eq!(searcher.search(&arr, &45), None, "{}", searcher.name());
+/// }
+/// ```
+pub trait Searcher<T: Ord> {
+    /// Название стратегии.
+    fn name(&self) -> &'static str;
+
+    /// Индекс элемента, равного `target`, или `None`, если такого нет.
+    /// Если равных элементов несколько, может вернуть любой из них.
+    fn search(&self, arr: &[T], target: &T) -> Option<usize>;
+}
+
+/// Линейный поиск, см. [`linear_search`]. Не требует сортировки.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LinearSearch;
+
+/// Бинарный поиск, см. [`binary_search`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BinarySearch;
+
+/// Бинарный поиск без ветвлений, см. [`branchless_binary_search`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchlessSearch;
+
+/// Поиск прыжками, см. [`jump_search`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JumpSearch;
+
+/// Поиск Фибоначчи, см. [`fibonacci_search`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FibonacciSearch;
+
+/// Тернарный поиск, см. [`ternary_search`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TernarySearch;
+
+/// Экспоненциальный поиск, см. [`exponential_search`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExponentialSearch;
+
+/// Интерполяционный поиск, см. [`interpolation_search`].
+/// Реализует [`Searcher`] только для целочисленных ключей, поэтому не входит в [`searchers`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InterpolationSearch;
+
+impl<T: NumericKey> Searcher<T> for InterpolationSearch {
+    fn name(&self) -> &'static str {
+        "interpolation"
+    }
+
+    fn search(&self, arr: &[T], target: &T) -> Option<usize> {
+        interpolation_search(arr, target)
+    }
+}
+
+macro_rules! impl_searcher {
+    ($($searcher:ident => $name:literal, $search:ident;)*) => {
+        $(
+            impl<T: Ord> Searcher<T> for $searcher {
+                fn name(&self) -> &'static str {
+                    $name
+                }
+
+                fn search(&self, arr: &[T], target: &T) -> Option<usize> {
+                    $search(arr, target)
+                }
+            }
+        )*
+    };
+}
+
+impl_searcher! {
+    LinearSearch => "linear", linear_search;
+    BinarySearch => "binary", binary_search;
+    BranchlessSearch => "branchless", branchless_binary_search;
+    JumpSearch => "jump", jump_search;
+    FibonacciSearch => "fibonacci", fibonacci_search;
+    TernarySearch => "ternary", ternary_search;
+    ExponentialSearch => "exponential", exponential_search;
+}
+
+/// Все стратегии поиска.
+pub fn searchers<T: Ord>() -> [&'static dyn Searcher<T>; 7] {
+    [
+        &LinearSearch,
+        &BinarySearch,
+        &BranchlessSearch,
+        &JumpSearch,
+        &FibonacciSearch,
+        &TernarySearch,
+        &ExponentialSearch,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Отсортированный массив с повторами: значения `0, 0, 2, 2, 4, ...`.
+    fn sorted(len: usize) -> Vec<u32> {
+        (0..len as u32).map(|i| i / 2 * 2).collect()
+    }
+
+    #[test]
+    fn test_all_searchers() {
+        for len in 0..100 {
+            let arr = sorted(len);
+            for target in 0..len as u32 + 2 {
+                let expected = arr.contains(&target);
+                for searcher in searchers() {
+                    let found = searcher.search(&arr, &target);
+                    let name = searcher.name();
+                    assert_eq!(found.is_some(), expected, "{name}, len = {len}, {target}");
+                    if let Some(index) = found {
+                        assert_eq!(arr[index], target, "{name}, len = {len}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_strings() {
+        let arr = ["абрикос", "вишня", "груша", "яблоко"].map(String::from);
+        for searcher in searchers() {
+            let target = String::from("груша");
+            assert_eq!(searcher.search(&arr, &target), Some(2));
+        }
+    }
+}
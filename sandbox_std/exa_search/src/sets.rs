@@ -0,0 +1,558 @@
+//! Операции над отсортированными последовательностями.
+//!
+//! Пересечение, объединение и разность отсортированных списков, например списков
+//! документов в обратном индексе, и слияние многих отсортированных итераторов.
+//!
+//! Последовательности считаются мультимножествами: элемент, встречающийся `a` раз в
+//! первой и `b` раз во второй, попадает в пересечение `min(a, b)` раз, в объединение —
+//! `max(a, b)` раз, в разность — `a - b` раз. Для списков без повторов это обычные
+//! операции над множествами.
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, binary_heap::PeekMut},
+    fmt,
+    iter::{FusedIterator, Peekable},
+};
+
+use crate::exponential_lower_bound;
+
+/// Галопирующее пересечение (Galloping Intersection)
+/// Принцип работы: элементы короткого среза по очереди ищутся в длинном
+/// экспоненциальным поиском от позиции предыдущей находки. Если длины сильно
+/// различаются, большая часть длинного среза пропускается, не читаясь.
+/// Когда использовать:
+///  * пересечение короткого списка с длинным (редкое слово с частым).
+///
+/// Характеристики:
+///  * Сложность по времени: O(m log(n / m)), где m и n — длины короткого и длинного
+///    срезов.
+///  * Сложность по памяти: O(1).
+///  * Требование: оба среза должны быть отсортированы.
+///
+/// Элементы берутся из короткого среза (из `a`, если длины равны).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::intersect_galloping;
+///
+/// let rare = [3, 40, 9000];
+/// let common: Vec<u32> = (0..10_000).step_by(2).collect();
+/// let found: Vec<u32> = intersect_galloping(&rare, &common).copied().collect();
+/// assert_eq!(found, [40, 9000]);
+/// ```
+pub fn intersect_galloping<'a, T: Ord>(a: &'a [T], b: &'a [T]) -> Intersection<'a, T> {
+    let (short, long) = if b.len() < a.len() { (b, a) } else { (a, b) };
+    Intersection { short, long }
+}
+
+/// Итератор по пересечению двух срезов, см. [`intersect_galloping`].
+#[derive(Debug, Clone)]
+pub struct Intersection<'a, T> {
+    /// Непросмотренные части срезов.
+    short: &'a [T],
+    long: &'a [T],
+}
+
+impl<'a, T: Ord> Iterator for Intersection<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        while let Some((element, rest)) = self.short.split_first() {
+            self.short = rest;
+            self.long = &self.long[exponential_lower_bound(self.long, element)..];
+            if let Some((found, rest)) = self.long.split_first()
+                && found == element
+            {
+                self.long = rest;
+                return Some(element);
+            }
+        }
+        None
+    }
+}
+
+impl<T: Ord> FusedIterator for Intersection<'_, T> {}
+
+/// Адаптивное пересечение (Adaptive Intersection) нескольких срезов
+/// Принцип работы: кандидат — очередной элемент самого короткого среза. Он
+/// последовательно ищется экспоненциальным поиском в остальных срезах; как только в
+/// каком-то срезе вместо него найден больший элемент, кандидатом становится первый
+/// элемент короткого среза, не меньший найденного. Число шагов зависит не только от длин
+/// срезов, но и от того, насколько перемешаны их значения: далеко разнесённые списки
+/// пересекаются за несколько прыжков.
+/// Когда использовать:
+///  * запрос из многих слов к обратному индексу.
+///
+/// Характеристики:
+///  * Сложность по времени: O(k · m log(n / m)) в худшем случае, где k — количество
+///    срезов, m — длина самого короткого.
+///  * Сложность по памяти: O(k).
+///  * Требование: все срезы должны быть отсортированы.
+///
+/// Пересечение пустого набора срезов пусто.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::intersect_adaptive;
+///
+/// let rust = [1, 4, 7, 9, 12, 15];
+/// let search = [2, 4, 9, 10, 15];
+/// let fast = [4, 5, 6, 9, 11, 15, 20];
+/// let found: Vec<u32> = intersect_adaptive(&[&rust, &search, &fast]).copied().collect();
+/// assert_eq!(found, [4, 9, 15]);
+/// ```
+pub fn intersect_adaptive<'a, T: Ord>(lists: &[&'a [T]]) -> AdaptiveIntersection<'a, T> {
+    let mut lists = lists.to_vec();
+    // Самый короткий срез — первым: он выбирает кандидатов.
+    if let Some(shortest) = (0..lists.len()).min_by_key(|&i| lists[i].len()) {
+        lists.swap(0, shortest);
+    }
+    AdaptiveIntersection { lists }
+}
+
+/// Итератор по пересечению нескольких срезов, см. [`intersect_adaptive`].
+#[derive(Debug, Clone)]
+pub struct AdaptiveIntersection<'a, T> {
+    /// Непросмотренные части срезов; первый — самый короткий.
+    lists: Vec<&'a [T]>,
+}
+
+impl<'a, T: Ord> Iterator for AdaptiveIntersection<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let (first, others) = self.lists.split_first_mut()?;
+        'candidates: loop {
+            let candidate = first.first()?;
+            for list in others.iter_mut() {
+                *list = &list[exponential_lower_bound(list, candidate)..];
+                let found = list.first()?;
+                if found > candidate {
+                    *first = &first[exponential_lower_bound(first, found)..];
+                    continue 'candidates;
+                }
+            }
+
+            // Кандидат есть во всех срезах.
+            *first = &first[1..];
+            for list in others.iter_mut() {
+                *list = &list[1..];
+            }
+            return Some(candidate);
+        }
+    }
+}
+
+impl<T: Ord> FusedIterator for AdaptiveIntersection<'_, T> {}
+
+/// Объединение двух отсортированных последовательностей
+/// Возвращает элементы обеих по возрастанию; равные элементы из разных
+/// последовательностей объединяются в один (берётся элемент из `a`).
+///
+/// Характеристики:
+///  * Сложность по времени: O(n + m).
+///  * Сложность по памяти: O(1).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::union;
+///
+/// let found: Vec<u32> = union([1, 3, 5, 7], [2, 3, 4, 7, 8]).collect();
+/// assert_eq!(found, [1, 2, 3, 4, 5, 7, 8]);
+/// ```
+pub fn union<A, B>(a: A, b: B) -> Union<A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator,
+    B: IntoIterator<Item = A::Item>,
+    A::Item: Ord,
+{
+    Union {
+        a: a.into_iter().peekable(),
+        b: b.into_iter().peekable(),
+    }
+}
+
+/// Итератор по объединению, см. [`union`].
+pub struct Union<A: Iterator, B: Iterator> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+// `derive` ограничил бы только `A` и `B`, а `Peekable` хранит ещё и элемент.
+impl<A, B> fmt::Debug for Union<A, B>
+where
+    A: Iterator<Item: fmt::Debug> + fmt::Debug,
+    B: Iterator<Item: fmt::Debug> + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Union")
+            .field("a", &self.a)
+            .field("b", &self.b)
+            .finish()
+    }
+}
+
+impl<A, B> Clone for Union<A, B>
+where
+    A: Iterator<Item: Clone> + Clone,
+    B: Iterator<Item: Clone> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+}
+
+impl<T: Ord, A, B> Iterator for Union<A, B>
+where
+    A: Iterator<Item = T>,
+    B: Iterator<Item = T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let order = match (self.a.peek(), self.b.peek()) {
+            (Some(x), Some(y)) => x.cmp(y),
+            (Some(_), None) => Ordering::Less,
+            (None, _) => Ordering::Greater,
+        };
+        match order {
+            Ordering::Less => self.a.next(),
+            Ordering::Greater => self.b.next(),
+            Ordering::Equal => {
+                self.b.next();
+                self.a.next()
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_low, a_high) = self.a.size_hint();
+        let (b_low, b_high) = self.b.size_hint();
+        let high = a_high.zip(b_high).and_then(|(a, b)| a.checked_add(b));
+        (a_low.max(b_low), high)
+    }
+}
+
+/// Разность двух отсортированных последовательностей
+/// Возвращает элементы `a`, которых нет в `b`, по возрастанию.
+///
+/// Характеристики:
+///  * Сложность по времени: O(n + m).
+///  * Сложность по памяти: O(1).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::difference;
+///
+/// let found: Vec<u32> = difference([1, 3, 5, 7, 9], [3, 4, 9]).collect();
+/// assert_eq!(found, [1, 5, 7]);
+/// ```
+pub fn difference<A, B>(a: A, b: B) -> Difference<A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator,
+    B: IntoIterator<Item = A::Item>,
+    A::Item: Ord,
+{
+    Difference {
+        a: a.into_iter(),
+        b: b.into_iter().peekable(),
+    }
+}
+
+/// Итератор по разности, см. [`difference`].
+pub struct Difference<A: Iterator, B: Iterator> {
+    a: A,
+    b: Peekable<B>,
+}
+
+impl<A, B> fmt::Debug for Difference<A, B>
+where
+    A: Iterator + fmt::Debug,
+    B: Iterator<Item: fmt::Debug> + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Difference")
+            .field("a", &self.a)
+            .field("b", &self.b)
+            .finish()
+    }
+}
+
+impl<A, B> Clone for Difference<A, B>
+where
+    A: Iterator + Clone,
+    B: Iterator<Item: Clone> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+}
+
+impl<T: Ord, A, B> Iterator for Difference<A, B>
+where
+    A: Iterator<Item = T>,
+    B: Iterator<Item = T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        for element in self.a.by_ref() {
+            // Пропускаем меньшие элементы `b`: они уже не встретятся в `a`.
+            while self.b.next_if(|other| *other < element).is_some() {}
+            if self.b.next_if(|other| *other == element).is_none() {
+                return Some(element);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.a.size_hint().1)
+    }
+}
+
+/// Слияние нескольких отсортированных итераторов (K-way Merge)
+/// Принцип работы: первые элементы итераторов хранятся в двоичной куче; на каждом
+/// шаге возвращается наименьший и на его место читается следующий элемент того же
+/// итератора. Равные элементы возвращаются в порядке итераторов.
+/// Когда использовать:
+///  * объединение многих отсортированных списков или потоков без их копирования.
+///
+/// Характеристики:
+///  * Сложность по времени: O(n log k), где n — общее количество элементов,
+///    k — количество итераторов.
+///  * Сложность по памяти: O(k).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::kmerge;
+///
+/// let merged: Vec<u32> = kmerge([vec![1, 4, 7], vec![2, 5, 8], vec![3, 6, 9]]).collect();
+/// assert_eq!(merged, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_search::kmerge;
+///
+/// let words = [["apple", "cherry"], ["banana", "date"]];
+/// let merged: Vec<&str> = kmerge(words).collect();
+/// assert_eq!(merged, ["apple", "banana", "cherry", "date"]);
+/// ```
+pub fn kmerge<I>(iters: I) -> KMerge<<I::Item as IntoIterator>::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+    <I::Item as IntoIterator>::Item: Ord,
+{
+    let mut iters: Vec<_> = iters.into_iter().map(IntoIterator::into_iter).collect();
+    let heap = iters
+        .iter_mut()
+        .enumerate()
+        .filter_map(|(index, iter)| iter.next().map(|element| Reverse((element, index))))
+        .collect();
+    KMerge { iters, heap }
+}
+
+/// Итератор слияния, см. [`kmerge`].
+pub struct KMerge<I: Iterator> {
+    iters: Vec<I>,
+    /// Очередные элементы итераторов с их номерами.
+    heap: BinaryHeap<Reverse<(I::Item, usize)>>,
+}
+
+impl<I> fmt::Debug for KMerge<I>
+where
+    I: Iterator<Item: fmt::Debug> + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KMerge")
+            .field("iters", &self.iters)
+            .field("heap", &self.heap)
+            .finish()
+    }
+}
+
+impl<I> Clone for KMerge<I>
+where
+    I: Iterator<Item: Clone> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            iters: self.iters.clone(),
+            heap: self.heap.clone(),
+        }
+    }
+}
+
+impl<I> Iterator for KMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let mut top = self.heap.peek_mut()?;
+        let Reverse((_, index)) = *top;
+        match self.iters[index].next() {
+            // Замена вершины с одним просеиванием вместо извлечения и вставки.
+            Some(next) => Some(std::mem::replace(&mut top.0, (next, index)).0),
+            None => Some(PeekMut::pop(top).0.0),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iters.iter().map(Iterator::size_hint).fold(
+            (self.heap.len(), Some(self.heap.len())),
+            |(low, high), (l, h)| {
+                let high = high.zip(h).and_then(|(a, b)| a.checked_add(b));
+                (low.saturating_add(l), high)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    /// Отсортированный список из `len` значений меньше `max`, возможно с повторами.
+    fn sorted(len: usize, max: u32, rng: &mut StdRng) -> Vec<u32> {
+        let mut list: Vec<u32> = (0..len).map(|_| rng.random_range(0..max)).collect();
+        list.sort();
+        list
+    }
+
+    /// Сколько раз `x` встречается в списке.
+    fn count(list: &[u32], x: u32) -> usize {
+        list.iter().filter(|&&y| y == x).count()
+    }
+
+    /// Мультимножество, где `x` встречается `times(x)` раз, для `x` из значений списков.
+    fn expected(lists: &[&[u32]], times: impl Fn(u32) -> usize) -> Vec<u32> {
+        let mut values: Vec<u32> = lists.concat();
+        values.sort();
+        values.dedup();
+        values
+            .into_iter()
+            .flat_map(|x| std::iter::repeat_n(x, times(x)))
+            .collect()
+    }
+
+    #[test]
+    fn test_two_lists() {
+        let mut rng = StdRng::seed_from_u64(0x5555_aaaa);
+        for round in 0..200 {
+            let a = sorted(round % 40, 50, &mut rng);
+            let b = sorted(round % 13 * 7, 50, &mut rng);
+            let lists: [&[u32]; 2] = [&a, &b];
+
+            let both = expected(&lists, |x| count(&a, x).min(count(&b, x)));
+            let galloping: Vec<u32> = intersect_galloping(&a, &b).copied().collect();
+            let adaptive: Vec<u32> = intersect_adaptive(&lists).copied().collect();
+            assert_eq!(galloping, both);
+            assert_eq!(adaptive, both);
+
+            let either = expected(&lists, |x| count(&a, x).max(count(&b, x)));
+            assert_eq!(union(&a, &b).copied().collect::<Vec<_>>(), either);
+
+            let only_a = expected(&lists, |x| count(&a, x).saturating_sub(count(&b, x)));
+            assert_eq!(difference(&a, &b).copied().collect::<Vec<_>>(), only_a);
+        }
+    }
+
+    #[test]
+    fn test_many_lists() {
+        let mut rng = StdRng::seed_from_u64(0x1357_2468);
+        for round in 0..100 {
+            let lists: Vec<Vec<u32>> = (0..round % 6)
+                .map(|i| sorted(10 + i * round % 50, 30, &mut rng))
+                .collect();
+            let slices: Vec<&[u32]> = lists.iter().map(Vec::as_slice).collect();
+
+            let all = if slices.is_empty() {
+                Vec::new()
+            } else {
+                expected(&slices, |x| {
+                    slices.iter().map(|list| count(list, x)).min().unwrap()
+                })
+            };
+            let found: Vec<u32> = intersect_adaptive(&slices).copied().collect();
+            assert_eq!(found, all);
+
+            let mut concatenated = slices.concat();
+            concatenated.sort();
+            let merged: Vec<u32> = kmerge(slices.iter().map(|list| list.iter().copied())).collect();
+            assert_eq!(merged, concatenated);
+        }
+    }
+
+    /// Элемент с ключом и меткой; порядок задаёт только ключ.
+    #[derive(Debug, Clone, Copy)]
+    struct Keyed(u32, char);
+
+    impl PartialEq for Keyed {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Keyed {}
+
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Keyed {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    #[test]
+    fn test_kmerge_stable() {
+        // Равные ключи выходят в порядке итераторов, хотя метки при сравнении не видны.
+        let lists = [
+            vec![Keyed(1, 'd'), Keyed(2, 'a')],
+            vec![Keyed(1, 'c'), Keyed(2, 'b')],
+            vec![],
+            vec![Keyed(1, 'b'), Keyed(1, 'a'), Keyed(3, 'c')],
+        ];
+        let labels: String = kmerge(lists).map(|element| element.1).collect();
+        assert_eq!(labels, "dcbaabc");
+    }
+
+    #[test]
+    fn test_debug_clone() {
+        let mut merged = kmerge([vec![1, 3], vec![2]]);
+        merged.next();
+        let rest: Vec<u32> = merged.clone().collect();
+        assert_eq!(rest, [2, 3]);
+        assert_eq!(merged.collect::<Vec<_>>(), rest);
+
+        let union = union([1, 2], [2, 3]);
+        assert!(format!("{union:?}").starts_with("Union"));
+        assert_eq!(union.clone().count(), 3);
+        assert_eq!(union.count(), 3);
+        let difference = difference([1, 2], [2]);
+        assert!(format!("{difference:?}").starts_with("Difference"));
+        assert_eq!(difference.clone().collect::<Vec<_>>(), [1]);
+        assert_eq!(difference.count(), 1);
+    }
+}
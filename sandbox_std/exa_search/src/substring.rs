@@ -0,0 +1,152 @@
+//! Поиск подстроки (Substring Search).
+//!
+//! Каждый алгоритм — отдельный поисковик, который один раз обрабатывает образец и затем
+//! ищет его в любом количестве текстов. Тексты и образцы — всё, что приводится к `&[u8]`:
+//! `&str`, `String`, `&[u8]`, `Vec<u8>`. Поиск возвращает итератор по позициям (в байтах)
+//! всех вхождений по возрастанию, включая перекрывающиеся: `"aa"` в `"aaa"` найдётся на
+//! позициях 0 и 1. Пустой образец встречается на каждой позиции от 0 до длины текста.
+//!
+//! Для текста в UTF-8 найденные позиции всегда приходятся на границы символов, так что
+//! ими можно резать `&str`.
+//!
+//! | Алгоритм              | Подготовка | Поиск          | Память |
+//! |-----------------------|------------|----------------|--------|
+//! | [`KmpFinder`]         | O(m)       | O(n)           | O(m)   |
+//! | [`HorspoolFinder`]    | O(m)       | O(n / m)…O(nm) | O(1)   |
+//! | [`RabinKarpFinder`]   | O(m)       | O(n)…O(nm)     | O(1)   |
+//! | [`ZFinder`]           | O(m)       | O(n)           | O(m)   |
+//!
+//! Для поиска многих образцов за один проход — [`AhoCorasick`].
+
+pub mod aho_corasick;
+pub mod horspool;
+pub mod kmp;
+pub mod rabin_karp;
+pub mod z_algorithm;
+
+pub use aho_corasick::{
+    AhoCorasick, AhoCorasickOptions, FindIter, Match, MatchKind, StreamFindIter,
+};
+pub use horspool::{HorspoolFinder, HorspoolMatches};
+pub use kmp::{KmpFinder, KmpMatches};
+pub use rabin_karp::{RabinKarpFinder, RabinKarpMatches};
+pub use z_algorithm::{ZFinder, ZMatches};
+
+/// Следующее вхождение пустого образца: каждая позиция от `pos` до `len` включительно.
+pub(crate) fn next_empty(pos: &mut usize, len: usize) -> Option<usize> {
+    let found = *pos;
+    (found <= len).then(|| {
+        *pos += 1;
+        found
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    /// Все позиции вхождений всеми поисковиками; проверяет, что они совпадают.
+    fn find_all(haystack: &[u8], pattern: &[u8]) -> Vec<usize> {
+        let kmp: Vec<usize> = KmpFinder::new(pattern).find_iter(haystack).collect();
+        let finders = [
+            (
+                "horspool",
+                HorspoolFinder::new(pattern).find_iter(haystack).collect(),
+            ),
+            (
+                "rabin_karp",
+                RabinKarpFinder::new(pattern).find_iter(haystack).collect(),
+            ),
+            (
+                "z",
+                ZFinder::new(pattern)
+                    .find_iter(haystack)
+                    .collect::<Vec<_>>(),
+            ),
+        ];
+        for (name, found) in finders {
+            assert_eq!(found, kmp, "{name}, {haystack:?}, {pattern:?}");
+        }
+        kmp
+    }
+
+    /// Перекрывающиеся вхождения перебором.
+    fn naive(haystack: &[u8], pattern: &[u8]) -> Vec<usize> {
+        (0..=haystack.len().saturating_sub(pattern.len()))
+            .filter(|&i| haystack[i..].starts_with(pattern))
+            .filter(|_| pattern.len() <= haystack.len())
+            .collect()
+    }
+
+    /// Оставляет неперекрывающиеся вхождения, выбирая их слева направо.
+    fn non_overlapping(positions: &[usize], len: usize) -> Vec<usize> {
+        let mut next = 0;
+        positions
+            .iter()
+            .copied()
+            .filter(|&position| {
+                let free = position >= next;
+                if free {
+                    next = position + len;
+                }
+                free
+            })
+            .collect()
+    }
+
+    /// Псевдослучайный текст из первых `alphabet` строчных букв.
+    fn text(len: usize, alphabet: u8, rng: &mut StdRng) -> String {
+        (0..len)
+            .map(|_| (b'a' + rng.random_range(0..alphabet)) as char)
+            .collect()
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(0x2468_ace0);
+        for alphabet in [1, 2, 4, 26] {
+            for len in [0, 1, 5, 50, 500] {
+                let haystack = text(len, alphabet, &mut rng);
+                for pattern_len in 1..8 {
+                    let pattern = text(pattern_len, alphabet, &mut rng);
+                    let found = find_all(haystack.as_bytes(), pattern.as_bytes());
+                    assert_eq!(found, naive(haystack.as_bytes(), pattern.as_bytes()));
+                    let expected: Vec<usize> =
+                        haystack.match_indices(&pattern).map(|(i, _)| i).collect();
+                    assert_eq!(non_overlapping(&found, pattern.len()), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        assert_eq!(find_all(b"aaaa", b"aa"), [0, 1, 2]);
+        assert_eq!(find_all(b"abc", b"abcd"), []);
+        assert_eq!(find_all(b"abc", b"abc"), [0]);
+        assert_eq!(find_all(b"", b"a"), []);
+        assert_eq!(find_all(b"abc", b""), [0, 1, 2, 3]);
+        assert_eq!(find_all(b"", b""), [0]);
+        // Все значения байта, включая нулевой.
+        let bytes: Vec<u8> = (0..=255).chain(0..=255).collect();
+        assert_eq!(find_all(&bytes, &[255, 0, 1]), [255]);
+        assert_eq!(find_all(&bytes, &[0]), [0, 256]);
+    }
+
+    #[test]
+    fn test_utf8() {
+        let haystack = "съешь же ещё этих мягких французских булок, да выпей же чаю";
+        for pattern in ["же", "ё", "их", "чаю", "с", "й ж"] {
+            let found = find_all(haystack.as_bytes(), pattern.as_bytes());
+            let expected: Vec<usize> = haystack.match_indices(pattern).map(|(i, _)| i).collect();
+            assert_eq!(
+                non_overlapping(&found, pattern.len()),
+                expected,
+                "{pattern}"
+            );
+            assert!(found.iter().all(|&i| haystack.is_char_boundary(i)));
+        }
+    }
+}
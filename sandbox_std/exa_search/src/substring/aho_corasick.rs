@@ -0,0 +1,641 @@
+//! Автомат Ахо — Корасик (Aho–Corasick) для поиска многих образцов за один проход.
+
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    io::{self, ErrorKind, Read},
+    ops::Range,
+};
+
+/// Размер блока, которым читается поток.
+const CHUNK: usize = 64 * 1024;
+
+/// Начальное состояние автомата (корень бора).
+const ROOT: u32 = 0;
+
+/// Какие вхождения сообщает автомат.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatchKind {
+    /// Неперекрывающиеся вхождения слева направо. Из вхождений, начинающихся в самой
+    /// левой позиции, выбирается образец, стоящий в списке раньше, как в альтернативе
+    /// регулярного выражения `a|b|c`.
+    #[default]
+    LeftmostFirst,
+    /// Все вхождения всех образцов, включая перекрывающиеся, в порядке их концов.
+    Overlapping,
+}
+
+/// Настройки автомата.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AhoCorasickOptions {
+    /// Какие вхождения сообщать.
+    pub match_kind: MatchKind,
+    /// Не различать регистр латинских букв (ASCII). Остальные байты сравниваются точно.
+    pub case_insensitive: bool,
+}
+
+/// Вхождение образца.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// Номер образца в списке, по которому построен автомат.
+    pub pattern: usize,
+    /// Позиция первого байта вхождения.
+    pub start: usize,
+    /// Позиция за последним байтом вхождения.
+    pub end: usize,
+}
+
+impl Match {
+    /// Диапазон байт вхождения.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// Поиск многих образцов автоматом Ахо — Корасик
+/// Принцип работы: образцы складываются в бор (префиксное дерево). Каждому узлу
+/// добавляется ссылка на узел наибольшего собственного суффикса, который тоже есть в боре,
+/// как грани в алгоритме Кнута — Морриса — Пратта. Переходы по ссылкам заранее сводятся в
+/// полную таблицу переходов, поэтому на каждый байт текста приходится ровно один переход,
+/// сколько бы ни было образцов.
+/// Когда использовать:
+///  * нужно найти в тексте любое из сотен и тысяч ключевых слов;
+///  * текст большой или поступает потоком, см. [`AhoCorasick::stream_find_iter`].
+///
+/// Характеристики:
+///  * Сложность по времени: O(256 · M) на построение, где M — суммарная длина образцов;
+///    O(n + k) на поиск, где k — количество вхождений.
+///  * Сложность по памяти: O(256 · M).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::AhoCorasick;
+///
+/// let ac = AhoCorasick::new(["error", "warn", "timeout"]);
+/// let found: Vec<(usize, usize)> = ac
+///     .find_iter("warn: request timeout, error 504")
+///     .map(|m| (m.pattern, m.start))
+///     .collect();
+/// assert_eq!(found, [(1, 0), (2, 14), (0, 23)]);
+/// ```
+///
+/// Все вхождения без учёта регистра:
+///
+/// ```
+/// use exa_search::{AhoCorasick, AhoCorasickOptions, MatchKind};
+///
+/// let options = AhoCorasickOptions {
+///     match_kind: MatchKind::Overlapping,
+///     case_insensitive: true,
+/// };
+/// let ac = AhoCorasick::with_options(["he", "she", "hers"], options);
+/// let found: Vec<_> = ac.find_iter("uSHERS").map(|m| m.range()).collect();
+/// assert_eq!(found, [1..4, 2..4, 2..6]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    options: AhoCorasickOptions,
+    /// Таблица переходов: `delta[state * 256 + byte]`.
+    delta: Vec<u32>,
+    /// Глубина узла в боре — длина строки, которую он представляет.
+    depth: Vec<usize>,
+    /// Образцы, заканчивающиеся в узле, включая найденные по суффиксным ссылкам.
+    outputs: Vec<Vec<usize>>,
+    /// Самый длинный из `outputs`, а среди равных по длине — первый в списке.
+    best: Vec<Option<usize>>,
+    /// Длины образцов.
+    lens: Vec<usize>,
+}
+
+impl AhoCorasick {
+    /// Строит автомат для поиска неперекрывающихся вхождений с учётом регистра.
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        Self::with_options(patterns, AhoCorasickOptions::default())
+    }
+
+    /// Строит автомат с заданными настройками.
+    pub fn with_options<I, P>(patterns: I, options: AhoCorasickOptions) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        // Бор: переход 0 означает отсутствие ребра, в корень рёбра не ведут.
+        let mut delta = vec![0; 256];
+        let mut depth = vec![0];
+        let mut outputs = vec![Vec::new()];
+        let mut lens = Vec::new();
+        for (index, pattern) in patterns.into_iter().enumerate() {
+            let mut state = ROOT as usize;
+            for &byte in pattern.as_ref() {
+                let byte = fold(byte, options.case_insensitive) as usize;
+                if delta[state * 256 + byte] == 0 {
+                    delta[state * 256 + byte] = depth.len() as u32;
+                    delta.extend([0; 256]);
+                    depth.push(depth[state] + 1);
+                    outputs.push(Vec::new());
+                }
+                state = delta[state * 256 + byte] as usize;
+            }
+            outputs[state].push(index);
+            lens.push(pattern.as_ref().len());
+        }
+
+        // Обход в ширину: суффиксная ссылка узла уже известна, когда до него доходит
+        // очередь, поэтому недостающие переходы берутся у неё.
+        let mut fail = vec![ROOT; depth.len()];
+        let mut queue = VecDeque::new();
+        queue.extend(delta[..256].iter().copied().filter(|&child| child != 0));
+        while let Some(state) = queue.pop_front() {
+            let (state, link) = (state as usize, fail[state as usize] as usize);
+            let inherited = outputs[link].clone();
+            outputs[state].extend(inherited);
+            for byte in 0..256 {
+                let child = delta[state * 256 + byte];
+                if child == 0 {
+                    delta[state * 256 + byte] = delta[link * 256 + byte];
+                } else {
+                    fail[child as usize] = delta[link * 256 + byte];
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        if options.case_insensitive {
+            for row in delta.chunks_exact_mut(256) {
+                row.copy_within(b'a' as usize..=b'z' as usize, b'A' as usize);
+            }
+        }
+        let best = outputs
+            .iter()
+            .map(|outputs| {
+                outputs
+                    .iter()
+                    .copied()
+                    .min_by_key(|&pattern| (usize::MAX - lens[pattern], pattern))
+            })
+            .collect();
+
+        Self {
+            options,
+            delta,
+            depth,
+            outputs,
+            best,
+            lens,
+        }
+    }
+
+    /// Количество образцов.
+    pub fn patterns_len(&self) -> usize {
+        self.lens.len()
+    }
+
+    /// Настройки, с которыми построен автомат.
+    pub fn options(&self) -> AhoCorasickOptions {
+        self.options
+    }
+
+    /// Первое вхождение в `haystack`.
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// use exa_search::AhoCorasick;
+    ///
+    /// // Из вхождений с одинаковым началом выигрывает образец, стоящий раньше.
+    /// let ac = AhoCorasick::new(["Sam", "Samwise"]);
+    /// assert_eq!(ac.find("Samwise").map(|m| m.pattern), Some(0));
+    ///
+    /// let ac = AhoCorasick::new(["Samwise", "Sam"]);
+    /// assert_eq!(ac.find("Samwise").map(|m| m.pattern), Some(0));
+    /// assert_eq!(ac.find("Frodo"), None);
+    /// ```
+    pub fn find<H: AsRef<[u8]> + ?Sized>(&self, haystack: &H) -> Option<Match> {
+        self.find_iter(haystack).next()
+    }
+
+    /// Итератор по вхождениям в `haystack` в соответствии с [`MatchKind`].
+    pub fn find_iter<'a, H: AsRef<[u8]> + ?Sized>(&'a self, haystack: &'a H) -> FindIter<'a> {
+        FindIter {
+            searcher: Searcher::new(self),
+            haystack: haystack.as_ref(),
+        }
+    }
+
+    /// Итератор по вхождениям в поток. Позиции отсчитываются от начала потока.
+    /// В памяти хранится лишь хвост прочитанного длиной не больше самого длинного
+    /// образца и блок чтения, поэтому поток может быть сколь угодно длинным.
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// use std::io::{BufReader, Cursor};
+    ///
+    /// use exa_search::AhoCorasick;
+    ///
+    /// let log = Cursor::new("INFO ok\nERROR disk full\nINFO ok\nERROR oom\n");
+    /// let ac = AhoCorasick::new(["ERROR", "WARN"]);
+    /// let starts: Vec<usize> = ac
+    ///     .stream_find_iter(BufReader::new(log))
+    ///     .map(|m| m.map(|m| m.start))
+    ///     .collect::<std::io::Result<_>>()?;
+    /// assert_eq!(starts, [8, 32]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn stream_find_iter<R: Read>(&self, reader: R) -> StreamFindIter<'_, R> {
+        StreamFindIter {
+            searcher: Searcher::new(self),
+            source: Buffered {
+                reader,
+                buf: Vec::new(),
+                offset: 0,
+                eof: false,
+            },
+        }
+    }
+}
+
+/// Байт после приведения регистра.
+fn fold(byte: u8, case_insensitive: bool) -> u8 {
+    if case_insensitive {
+        byte.to_ascii_lowercase()
+    } else {
+        byte
+    }
+}
+
+/// Итератор по вхождениям в срез, см. [`AhoCorasick::find_iter`].
+#[derive(Debug)]
+pub struct FindIter<'a> {
+    searcher: Searcher<'a>,
+    haystack: &'a [u8],
+}
+
+impl Iterator for FindIter<'_> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        let Ok(found) = self.searcher.next(&mut self.haystack);
+        found
+    }
+}
+
+/// Итератор по вхождениям в поток, см. [`AhoCorasick::stream_find_iter`].
+#[derive(Debug)]
+pub struct StreamFindIter<'a, R> {
+    searcher: Searcher<'a>,
+    source: Buffered<R>,
+}
+
+impl<R: Read> Iterator for StreamFindIter<'_, R> {
+    type Item = io::Result<Match>;
+
+    fn next(&mut self) -> Option<io::Result<Match>> {
+        self.searcher.next(&mut self.source).transpose()
+    }
+}
+
+/// Источник байт для [`Searcher`].
+trait Source {
+    type Error;
+
+    /// Байт на позиции `searcher.pos` или `None`, если данные закончились.
+    fn byte(&mut self, searcher: &Searcher) -> Result<Option<u8>, Self::Error>;
+}
+
+impl Source for &[u8] {
+    type Error = Infallible;
+
+    fn byte(&mut self, searcher: &Searcher) -> Result<Option<u8>, Infallible> {
+        Ok(self.get(searcher.pos).copied())
+    }
+}
+
+/// Поток, от которого в памяти хранится лишь ещё нужный хвост прочитанного.
+#[derive(Debug)]
+struct Buffered<R> {
+    reader: R,
+    /// Прочитанные байты начиная с позиции `offset`.
+    buf: Vec<u8>,
+    offset: usize,
+    eof: bool,
+}
+
+impl<R: Read> Buffered<R> {
+    /// Отбрасывает байты, которые больше не понадобятся, и дочитывает блок.
+    fn refill(&mut self, searcher: &Searcher) -> io::Result<()> {
+        // Перечитывать придётся не раньше начала кандидата и начала текущего префикса.
+        let live = searcher.live();
+        let keep = searcher
+            .candidate
+            .map_or(live, |found| found.start.min(live));
+        self.buf.drain(..keep - self.offset);
+        self.offset = keep;
+
+        let len = self.buf.len();
+        self.buf.resize(len + CHUNK, 0);
+        let read = loop {
+            match self.reader.read(&mut self.buf[len..]) {
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        };
+        self.buf.truncate(len + *read.as_ref().unwrap_or(&0));
+        self.eof = read? == 0;
+        Ok(())
+    }
+}
+
+impl<R: Read> Source for Buffered<R> {
+    type Error = io::Error;
+
+    fn byte(&mut self, searcher: &Searcher) -> io::Result<Option<u8>> {
+        while searcher.pos - self.offset >= self.buf.len() {
+            if self.eof {
+                return Ok(None);
+            }
+            self.refill(searcher)?;
+        }
+        Ok(Some(self.buf[searcher.pos - self.offset]))
+    }
+}
+
+/// Состояние поиска, общее для среза и потока.
+#[derive(Debug)]
+struct Searcher<'a> {
+    automaton: &'a AhoCorasick,
+    /// Позиция следующего байта и состояние автомата после байт до неё.
+    pos: usize,
+    state: u32,
+    /// Сколько вхождений из `outputs[state]` уже сообщено (режим `Overlapping`).
+    emitted: usize,
+    /// Самое левое найденное, но ещё не сообщённое вхождение (режим `LeftmostFirst`).
+    candidate: Option<Match>,
+    /// Пропустить байт перед продолжением поиска: последнее вхождение было пустым.
+    skip: bool,
+    finished: bool,
+}
+
+impl<'a> Searcher<'a> {
+    fn new(automaton: &'a AhoCorasick) -> Self {
+        Self {
+            automaton,
+            pos: 0,
+            state: ROOT,
+            emitted: 0,
+            candidate: None,
+            skip: false,
+            finished: false,
+        }
+    }
+
+    /// Начало текущего префикса бора: раньше него будущие вхождения не начинаются.
+    fn live(&self) -> usize {
+        self.pos - self.automaton.depth[self.state as usize]
+    }
+
+    fn advance(&mut self, byte: u8) {
+        self.state = self.automaton.delta[self.state as usize * 256 + byte as usize];
+        self.pos += 1;
+    }
+
+    fn next<S: Source>(&mut self, source: &mut S) -> Result<Option<Match>, S::Error> {
+        match self.automaton.options.match_kind {
+            MatchKind::LeftmostFirst => self.next_leftmost(source),
+            MatchKind::Overlapping => self.next_overlapping(source),
+        }
+    }
+
+    fn next_overlapping<S: Source>(&mut self, source: &mut S) -> Result<Option<Match>, S::Error> {
+        loop {
+            let outputs = &self.automaton.outputs[self.state as usize];
+            if let Some(&pattern) = outputs.get(self.emitted) {
+                self.emitted += 1;
+                return Ok(Some(Match {
+                    pattern,
+                    start: self.pos - self.automaton.lens[pattern],
+                    end: self.pos,
+                }));
+            }
+            let Some(byte) = source.byte(self)? else {
+                return Ok(None);
+            };
+            self.advance(byte);
+            self.emitted = 0;
+        }
+    }
+
+    fn next_leftmost<S: Source>(&mut self, source: &mut S) -> Result<Option<Match>, S::Error> {
+        if self.finished {
+            return Ok(None);
+        }
+        if self.skip {
+            self.skip = false;
+            if source.byte(self)?.is_none() {
+                self.finished = true;
+                return Ok(None);
+            }
+            self.pos += 1;
+        }
+
+        loop {
+            if let Some(pattern) = self.automaton.best[self.state as usize] {
+                let found = Match {
+                    pattern,
+                    start: self.pos - self.automaton.lens[pattern],
+                    end: self.pos,
+                };
+                if self
+                    .candidate
+                    .is_none_or(|best| (found.start, found.pattern) < (best.start, best.pattern))
+                {
+                    self.candidate = Some(found);
+                }
+            }
+
+            // Будущие вхождения начинаются не раньше текущего префикса бора: если он
+            // начался позже кандидата, кандидат окончательный.
+            let byte = match self.candidate {
+                Some(found) if found.start < self.live() => None,
+                _ => source.byte(self)?,
+            };
+            match byte {
+                Some(byte) => self.advance(byte),
+                None => {
+                    let Some(found) = self.candidate.take() else {
+                        self.finished = true;
+                        return Ok(None);
+                    };
+                    // Поиск продолжается с конца вхождения.
+                    self.pos = found.end;
+                    self.state = ROOT;
+                    self.skip = found.start == found.end;
+                    return Ok(Some(found));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    /// Все вхождения перебором, упорядоченные по концу.
+    fn naive_overlapping(haystack: &[u8], patterns: &[Vec<u8>]) -> Vec<Match> {
+        let mut found = Vec::new();
+        for end in 0..=haystack.len() {
+            for (pattern, bytes) in patterns.iter().enumerate() {
+                if haystack[..end].ends_with(bytes) {
+                    let start = end - bytes.len();
+                    found.push(Match {
+                        pattern,
+                        start,
+                        end,
+                    });
+                }
+            }
+        }
+        found
+    }
+
+    /// Самые левые первые вхождения перебором.
+    fn naive_leftmost(haystack: &[u8], patterns: &[Vec<u8>]) -> Vec<Match> {
+        let mut found = Vec::new();
+        let mut start = 0;
+        while start <= haystack.len() {
+            let first = patterns
+                .iter()
+                .position(|bytes| haystack[start..].starts_with(bytes));
+            match first {
+                Some(pattern) => {
+                    let end = start + patterns[pattern].len();
+                    found.push(Match {
+                        pattern,
+                        start,
+                        end,
+                    });
+                    start = if end == start { end + 1 } else { end };
+                }
+                None => start += 1,
+            }
+        }
+        found
+    }
+
+    /// Поток, который отдаёт данные кусками по 1–3 байта.
+    struct Trickle<'a>(&'a [u8], usize);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.1 = self.1 % 3 + 1;
+            let len = self.1.min(buf.len()).min(self.0.len());
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    fn check(haystack: &[u8], patterns: &[Vec<u8>]) {
+        for (match_kind, expected) in [
+            (MatchKind::LeftmostFirst, naive_leftmost(haystack, patterns)),
+            (
+                MatchKind::Overlapping,
+                naive_overlapping(haystack, patterns),
+            ),
+        ] {
+            let options = AhoCorasickOptions {
+                match_kind,
+                ..Default::default()
+            };
+            let ac = AhoCorasick::with_options(patterns, options);
+            let mut found: Vec<Match> = ac.find_iter(haystack).collect();
+            let streamed: Vec<Match> = ac
+                .stream_find_iter(Trickle(haystack, 0))
+                .collect::<io::Result<_>>()
+                .unwrap();
+            assert_eq!(streamed, found, "{match_kind:?}");
+            if match_kind == MatchKind::Overlapping {
+                // Порядок вхождений с общим концом не задан.
+                found.sort_by_key(|found| (found.end, found.pattern));
+            }
+            assert_eq!(
+                found, expected,
+                "{match_kind:?}, {haystack:?}, {patterns:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(0x0bad_cafe);
+        let mut random =
+            |len: usize| -> Vec<u8> { (0..len).map(|_| b'a' + rng.random_range(0..3)).collect() };
+        for round in 0..200 {
+            let haystack = random(round % 60);
+            let patterns: Vec<Vec<u8>> = (0..round % 7 + 1).map(|i| random(i % 4 + 1)).collect();
+            check(&haystack, &patterns);
+        }
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        check(b"abc", &[]);
+        check(b"", &[b"a".to_vec()]);
+        check(b"abc", &[b"".to_vec()]);
+        check(b"", &[b"".to_vec()]);
+        check(b"ab", &[b"".to_vec(), b"a".to_vec()]);
+        check(b"abcd", &[b"b".to_vec(), b"abcd".to_vec(), b"bc".to_vec()]);
+        check(b"abce", &[b"abcd".to_vec(), b"bc".to_vec(), b"c".to_vec()]);
+        check(b"aaaa", &[b"aa".to_vec(), b"aa".to_vec(), b"a".to_vec()]);
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        let options = AhoCorasickOptions {
+            case_insensitive: true,
+            ..Default::default()
+        };
+        let ac = AhoCorasick::with_options(["GET", "Post", "ошибка"], options);
+        let found: Vec<usize> = ac
+            .find_iter("get /, POST /api, post, Ошибка, ошибка")
+            .map(|found| found.start)
+            .collect();
+        // Кириллица сравнивается с учётом регистра.
+        assert_eq!(found, [0, 7, 18, 38]);
+    }
+
+    #[test]
+    fn test_stream_long() {
+        // Вхождения на границах блоков чтения.
+        let mut haystack = vec![b'.'; 3 * CHUNK];
+        for (at, bytes) in [
+            (0, "needl"),
+            (CHUNK - 2, "needl"),
+            (2 * CHUNK - 3, "needle"),
+            (3 * CHUNK - 5, "needl"),
+        ] {
+            haystack[at..at + bytes.len()].copy_from_slice(bytes.as_bytes());
+        }
+        let ac = AhoCorasick::new(["needle", "needl"]);
+        let found: Vec<(usize, usize)> = ac
+            .stream_find_iter(haystack.as_slice())
+            .map(|found| found.map(|found| (found.pattern, found.start)))
+            .collect::<io::Result<_>>()
+            .unwrap();
+        let expected = [
+            (1, 0),
+            (1, CHUNK - 2),
+            (0, 2 * CHUNK - 3),
+            (1, 3 * CHUNK - 5),
+        ];
+        assert_eq!(found, expected);
+    }
+}
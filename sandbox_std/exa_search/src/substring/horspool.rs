@@ -0,0 +1,116 @@
+//! Алгоритм Бойера — Мура — Хорспула (Boyer–Moore–Horspool).
+
+use std::borrow::Cow;
+
+use super::next_empty;
+
+/// Поиск подстроки алгоритмом Бойера — Мура — Хорспула
+/// Принцип работы: окно длины образца сравнивается с образцом, а затем сдвигается по
+/// последнему байту окна: на расстояние от последнего вхождения этого байта в образец
+/// (не считая последней позиции) до конца образца. Байт, которого нет в образце,
+/// сдвигает окно сразу на всю длину образца.
+/// Когда использовать:
+///  * длинные образцы и большой алфавит (обычный текст): в среднем читается лишь
+///    часть байт текста.
+///
+/// Характеристики:
+///  * Сложность по времени: O(m + 256) на подготовку, O(n / m) в лучшем случае и
+///    O(nm) в худшем (например, `"aaa…"` в `"aaaa…"`).
+///  * Сложность по памяти: O(1), таблица сдвигов на 256 значений байта.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::HorspoolFinder;
+///
+/// let finder = HorspoolFinder::new("needle");
+/// let found: Vec<usize> = finder.find_iter("haystack with a needle and a needle").collect();
+/// assert_eq!(found, [16, 29]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HorspoolFinder<'p> {
+    pattern: &'p [u8],
+    /// Сдвиг окна по его последнему байту.
+    shift: Box<[usize; 256]>,
+}
+
+impl<'p> HorspoolFinder<'p> {
+    /// Подготавливает образец за O(m).
+    pub fn new<P: AsRef<[u8]> + ?Sized>(pattern: &'p P) -> Self {
+        let pattern = pattern.as_ref();
+        let len = pattern.len();
+        let mut shift = Box::new([len; 256]);
+        for (i, &byte) in pattern.iter().enumerate().take(len.saturating_sub(1)) {
+            shift[byte as usize] = len - 1 - i;
+        }
+        Self { pattern, shift }
+    }
+
+    /// Итератор по позициям всех вхождений образца в `haystack`.
+    pub fn find_iter<'a, H: AsRef<[u8]> + ?Sized>(
+        &'a self,
+        haystack: &'a H,
+    ) -> HorspoolMatches<'a> {
+        HorspoolMatches {
+            pattern: self.pattern,
+            shift: Cow::Borrowed(&self.shift),
+            haystack: haystack.as_ref(),
+            pos: 0,
+        }
+    }
+}
+
+/// Итератор по вхождениям, см. [`HorspoolFinder::find_iter`] и [`horspool_find_iter`].
+#[derive(Debug, Clone)]
+pub struct HorspoolMatches<'a> {
+    pattern: &'a [u8],
+    shift: Cow<'a, [usize; 256]>,
+    haystack: &'a [u8],
+    /// Начало следующего окна.
+    pos: usize,
+}
+
+impl Iterator for HorspoolMatches<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let len = self.pattern.len();
+        if len == 0 {
+            return next_empty(&mut self.pos, self.haystack.len());
+        }
+
+        while let Some(window) = self.haystack.get(self.pos..self.pos + len) {
+            let start = self.pos;
+            // Сдвиг безопасен: образец не может начинаться между `start` и новым окном.
+            self.pos += self.shift[window[len - 1] as usize];
+            if window == self.pattern {
+                return Some(start);
+            }
+        }
+        None
+    }
+}
+
+/// Позиции всех вхождений `pattern` в `haystack` алгоритмом Бойера — Мура — Хорспула.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::horspool_find_iter;
+///
+/// let found: Vec<usize> = horspool_find_iter(b"GCATCGCAGAGAGTATACAGTACG", b"GAG").collect();
+/// assert_eq!(found, [8, 10]);
+/// ```
+pub fn horspool_find_iter<'a, H, P>(haystack: &'a H, pattern: &'a P) -> HorspoolMatches<'a>
+where
+    H: AsRef<[u8]> + ?Sized,
+    P: AsRef<[u8]> + ?Sized,
+{
+    let HorspoolFinder { pattern, shift } = HorspoolFinder::new(pattern);
+    HorspoolMatches {
+        pattern,
+        shift: Cow::Owned(*shift),
+        haystack: haystack.as_ref(),
+        pos: 0,
+    }
+}
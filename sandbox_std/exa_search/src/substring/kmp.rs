@@ -0,0 +1,135 @@
+//! Алгоритм Кнута — Морриса — Пратта (Knuth–Morris–Pratt).
+
+use std::borrow::Cow;
+
+use super::next_empty;
+
+/// Поиск подстроки алгоритмом Кнута — Морриса — Пратта
+/// Принцип работы: для каждого префикса образца заранее вычисляется длина его
+/// наибольшей собственной грани (префикса, равного суффиксу). При несовпадении образец
+/// сдвигается так, чтобы уже совпавшая часть текста совпала с гранью, поэтому текст
+/// читается строго слева направо и ни один байт не читается повторно.
+/// Когда использовать:
+///  * нужна гарантия O(n + m) в худшем случае;
+///  * текст поступает потоком и возвращаться назад нельзя.
+///
+/// Характеристики:
+///  * Сложность по времени: O(m) на подготовку, O(n) на поиск.
+///  * Сложность по памяти: O(m).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::KmpFinder;
+///
+/// let finder = KmpFinder::new("abab");
+/// let found: Vec<usize> = finder.find_iter("abababab").collect();
+/// assert_eq!(found, [0, 2, 4]);
+/// ```
+///
+/// ```
+/// use exa_search::KmpFinder;
+///
+/// let finder = KmpFinder::new(&[0xff, 0x00]);
+/// assert_eq!(finder.find_iter(&[1, 0xff, 0x00, 0xff]).next(), Some(1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct KmpFinder<'p> {
+    pattern: &'p [u8],
+    /// `failure[i]` — длина наибольшей собственной грани `pattern[..=i]`.
+    failure: Vec<usize>,
+}
+
+impl<'p> KmpFinder<'p> {
+    /// Подготавливает образец за O(m).
+    pub fn new<P: AsRef<[u8]> + ?Sized>(pattern: &'p P) -> Self {
+        let pattern = pattern.as_ref();
+        let mut failure = vec![0; pattern.len()];
+        let mut border = 0;
+        for i in 1..pattern.len() {
+            while border > 0 && pattern[i] != pattern[border] {
+                border = failure[border - 1];
+            }
+            if pattern[i] == pattern[border] {
+                border += 1;
+            }
+            failure[i] = border;
+        }
+        Self { pattern, failure }
+    }
+
+    /// Итератор по позициям всех вхождений образца в `haystack`.
+    pub fn find_iter<'a, H: AsRef<[u8]> + ?Sized>(&'a self, haystack: &'a H) -> KmpMatches<'a> {
+        KmpMatches {
+            pattern: self.pattern,
+            failure: Cow::Borrowed(&self.failure),
+            haystack: haystack.as_ref(),
+            pos: 0,
+            matched: 0,
+        }
+    }
+}
+
+/// Итератор по вхождениям, см. [`KmpFinder::find_iter`] и [`kmp_find_iter`].
+#[derive(Debug, Clone)]
+pub struct KmpMatches<'a> {
+    pattern: &'a [u8],
+    failure: Cow<'a, [usize]>,
+    haystack: &'a [u8],
+    /// Следующий читаемый байт текста.
+    pos: usize,
+    /// Длина совпавшего префикса образца.
+    matched: usize,
+}
+
+impl Iterator for KmpMatches<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let len = self.pattern.len();
+        if len == 0 {
+            return next_empty(&mut self.pos, self.haystack.len());
+        }
+
+        while let Some(&byte) = self.haystack.get(self.pos) {
+            self.pos += 1;
+            while self.matched > 0 && self.pattern[self.matched] != byte {
+                self.matched = self.failure[self.matched - 1];
+            }
+            if self.pattern[self.matched] == byte {
+                self.matched += 1;
+            }
+            if self.matched == len {
+                // Продолжаем с грани образца: так находятся и перекрывающиеся вхождения.
+                self.matched = self.failure[len - 1];
+                return Some(self.pos - len);
+            }
+        }
+        None
+    }
+}
+
+/// Позиции всех вхождений `pattern` в `haystack` алгоритмом Кнута — Морриса — Пратта.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::kmp_find_iter;
+///
+/// let found: Vec<usize> = kmp_find_iter("мама мыла раму", "ма").collect();
+/// assert_eq!(found, [0, 4]);
+/// ```
+pub fn kmp_find_iter<'a, H, P>(haystack: &'a H, pattern: &'a P) -> KmpMatches<'a>
+where
+    H: AsRef<[u8]> + ?Sized,
+    P: AsRef<[u8]> + ?Sized,
+{
+    let KmpFinder { pattern, failure } = KmpFinder::new(pattern);
+    KmpMatches {
+        pattern,
+        failure: Cow::Owned(failure),
+        haystack: haystack.as_ref(),
+        pos: 0,
+        matched: 0,
+    }
+}
@@ -0,0 +1,136 @@
+//! Алгоритм Рабина — Карпа (Rabin–Karp).
+
+use super::next_empty;
+
+/// Основание полиномиального хеша. Вычисления ведутся по модулю 2^64.
+const BASE: u64 = 0x100_0000_01b3;
+
+/// Поиск подстроки алгоритмом Рабина — Карпа
+/// Принцип работы: сравниваются не окна текста, а их полиномиальные хеши. Хеш
+/// следующего окна получается из предыдущего за O(1): вычитается вклад ушедшего байта и
+/// добавляется пришедший (скользящий хеш). При совпадении хешей окно сверяется с
+/// образцом побайтно, поэтому коллизии не дают ложных вхождений.
+/// Когда использовать:
+///  * основа для поиска многих образцов одной длины (хеши образцов в множестве);
+///  * поиск повторяющихся подстрок.
+///
+/// Характеристики:
+///  * Сложность по времени: O(m) на подготовку, O(n) в среднем, O(nm) в худшем случае
+///    (много вхождений или коллизий).
+///  * Сложность по памяти: O(1).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::RabinKarpFinder;
+///
+/// let finder = RabinKarpFinder::new("ana");
+/// let found: Vec<usize> = finder.find_iter("bananas").collect();
+/// assert_eq!(found, [1, 3]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RabinKarpFinder<'p> {
+    pattern: &'p [u8],
+    /// Хеш образца.
+    hash: u64,
+    /// `BASE^(m - 1)`: вклад первого байта окна.
+    power: u64,
+}
+
+impl<'p> RabinKarpFinder<'p> {
+    /// Подготавливает образец за O(m).
+    pub fn new<P: AsRef<[u8]> + ?Sized>(pattern: &'p P) -> Self {
+        let pattern = pattern.as_ref();
+        let power = (1..pattern.len()).fold(1, |power: u64, _| power.wrapping_mul(BASE));
+        Self {
+            pattern,
+            hash: hash(pattern),
+            power,
+        }
+    }
+
+    /// Итератор по позициям всех вхождений образца в `haystack`.
+    pub fn find_iter<'a, H: AsRef<[u8]> + ?Sized>(&self, haystack: &'a H) -> RabinKarpMatches<'a>
+    where
+        'p: 'a,
+    {
+        let haystack = haystack.as_ref();
+        let window = haystack.get(..self.pattern.len()).map_or(0, hash);
+        RabinKarpMatches {
+            finder: *self,
+            haystack,
+            pos: 0,
+            window,
+        }
+    }
+}
+
+/// Полиномиальный хеш `s[0]·BASE^(m-1) + … + s[m-1]` по модулю 2^64.
+fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |hash: u64, &byte| {
+        hash.wrapping_mul(BASE).wrapping_add(byte as u64)
+    })
+}
+
+/// Итератор по вхождениям, см. [`RabinKarpFinder::find_iter`] и [`rabin_karp_find_iter`].
+#[derive(Debug, Clone)]
+pub struct RabinKarpMatches<'a> {
+    finder: RabinKarpFinder<'a>,
+    haystack: &'a [u8],
+    /// Начало текущего окна.
+    pos: usize,
+    /// Хеш текущего окна.
+    window: u64,
+}
+
+impl Iterator for RabinKarpMatches<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let RabinKarpFinder {
+            pattern,
+            hash,
+            power,
+        } = self.finder;
+        let len = pattern.len();
+        if len == 0 {
+            return next_empty(&mut self.pos, self.haystack.len());
+        }
+
+        while self.pos + len <= self.haystack.len() {
+            let start = self.pos;
+            let found = self.window == hash && self.haystack[start..start + len] == *pattern;
+            if let Some(&incoming) = self.haystack.get(start + len) {
+                let outgoing = self.haystack[start] as u64;
+                self.window = self
+                    .window
+                    .wrapping_sub(outgoing.wrapping_mul(power))
+                    .wrapping_mul(BASE)
+                    .wrapping_add(incoming as u64);
+            }
+            self.pos += 1;
+            if found {
+                return Some(start);
+            }
+        }
+        None
+    }
+}
+
+/// Позиции всех вхождений `pattern` в `haystack` алгоритмом Рабина — Карпа.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::rabin_karp_find_iter;
+///
+/// let found: Vec<usize> = rabin_karp_find_iter(&[1, 2, 1, 2, 1], &[1, 2, 1]).collect();
+/// assert_eq!(found, [0, 2]);
+/// ```
+pub fn rabin_karp_find_iter<'a, H, P>(haystack: &'a H, pattern: &'a P) -> RabinKarpMatches<'a>
+where
+    H: AsRef<[u8]> + ?Sized,
+    P: AsRef<[u8]> + ?Sized,
+{
+    RabinKarpFinder::new(pattern).find_iter(haystack)
+}
@@ -0,0 +1,166 @@
+//! Z-алгоритм (Z-Algorithm).
+
+use std::borrow::Cow;
+
+use super::next_empty;
+
+/// Z-функция строки
+/// `z[i]` — длина наибольшего общего префикса `s` и `s[i..]`; `z[0]` равен длине `s`.
+/// Вычисляется за O(n) с помощью самого правого известного отрезка `[l, r)`,
+/// совпадающего с префиксом строки.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::z_function;
+///
+/// assert_eq!(z_function("aabxaab"), [7, 1, 0, 0, 3, 1, 0]);
+/// assert_eq!(z_function(""), []);
+/// ```
+pub fn z_function<S: AsRef<[u8]> + ?Sized>(s: &S) -> Vec<usize> {
+    let s = s.as_ref();
+    let mut z = vec![0; s.len()];
+    let (mut left, mut right) = (0, 0);
+    for i in 1..s.len() {
+        let mut len = if i < right {
+            z[i - left].min(right - i)
+        } else {
+            0
+        };
+        while i + len < s.len() && s[len] == s[i + len] {
+            len += 1;
+        }
+        if i + len > right {
+            (left, right) = (i, i + len);
+        }
+        z[i] = len;
+    }
+    if let Some(first) = z.first_mut() {
+        *first = s.len();
+    }
+    z
+}
+
+/// Поиск подстроки Z-алгоритмом
+/// Принцип работы: для каждой позиции текста вычисляется длина общего префикса
+/// остатка текста и образца, как в [`z_function`] для строки «образец + текст», но без
+/// склейки: известный отрезок `[l, r)` текста, совпадающий с началом образца, позволяет
+/// взять длину из Z-функции образца и продолжать сравнение только правее `r`.
+/// Вхождение — позиция, где общий префикс равен длине образца.
+/// Когда использовать:
+///  * нужна гарантия O(n + m) и простая реализация;
+///  * кроме вхождений нужны длины совпадений префикса образца на каждой позиции.
+///
+/// Характеристики:
+///  * Сложность по времени: O(m) на подготовку, O(n) на поиск.
+///  * Сложность по памяти: O(m).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::ZFinder;
+///
+/// let finder = ZFinder::new("aab");
+/// let found: Vec<usize> = finder.find_iter("aabxaaabaab").collect();
+/// assert_eq!(found, [0, 5, 8]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZFinder<'p> {
+    pattern: &'p [u8],
+    z: Vec<usize>,
+}
+
+impl<'p> ZFinder<'p> {
+    /// Подготавливает образец за O(m).
+    pub fn new<P: AsRef<[u8]> + ?Sized>(pattern: &'p P) -> Self {
+        let pattern = pattern.as_ref();
+        Self {
+            pattern,
+            z: z_function(pattern),
+        }
+    }
+
+    /// Итератор по позициям всех вхождений образца в `haystack`.
+    pub fn find_iter<'a, H: AsRef<[u8]> + ?Sized>(&'a self, haystack: &'a H) -> ZMatches<'a> {
+        ZMatches {
+            pattern: self.pattern,
+            z: Cow::Borrowed(&self.z),
+            haystack: haystack.as_ref(),
+            pos: 0,
+            left: 0,
+            right: 0,
+        }
+    }
+}
+
+/// Итератор по вхождениям, см. [`ZFinder::find_iter`] и [`z_find_iter`].
+#[derive(Debug, Clone)]
+pub struct ZMatches<'a> {
+    pattern: &'a [u8],
+    z: Cow<'a, [usize]>,
+    haystack: &'a [u8],
+    /// Следующая проверяемая позиция текста.
+    pos: usize,
+    /// `haystack[left..right]` совпадает с `pattern[..right - left]`.
+    left: usize,
+    right: usize,
+}
+
+impl Iterator for ZMatches<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let (pattern, haystack) = (self.pattern, self.haystack);
+        if pattern.is_empty() {
+            return next_empty(&mut self.pos, haystack.len());
+        }
+
+        while self.pos + pattern.len() <= haystack.len() {
+            let i = self.pos;
+            self.pos += 1;
+            // Внутри известного отрезка длина берётся из Z-функции образца. Если она
+            // упирается в `right`, сравнение продолжается за ним.
+            let mut len = if i < self.right {
+                self.z[i - self.left].min(self.right - i)
+            } else {
+                0
+            };
+            if i + len >= self.right {
+                while len < pattern.len() && haystack.get(i + len) == Some(&pattern[len]) {
+                    len += 1;
+                }
+                (self.left, self.right) = (i, i + len);
+            }
+            if len == pattern.len() {
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
+/// Позиции всех вхождений `pattern` в `haystack` Z-алгоритмом.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::z_find_iter;
+///
+/// let found: Vec<usize> = z_find_iter("abracadabra", "abra").collect();
+/// assert_eq!(found, [0, 7]);
+/// ```
+pub fn z_find_iter<'a, H, P>(haystack: &'a H, pattern: &'a P) -> ZMatches<'a>
+where
+    H: AsRef<[u8]> + ?Sized,
+    P: AsRef<[u8]> + ?Sized,
+{
+    let ZFinder { pattern, z } = ZFinder::new(pattern);
+    ZMatches {
+        pattern,
+        z: Cow::Owned(z),
+        haystack: haystack.as_ref(),
+        pos: 0,
+        left: 0,
+        right: 0,
+    }
+}
@@ -0,0 +1,61 @@
+//! Тернарный поиск (Ternary Search).
+
+use std::cmp::Ordering;
+
+/// Тернарный поиск (Ternary Search)
+/// Принцип работы: на каждом шаге диапазон делится двумя точками на три части,
+/// и поиск продолжается в той, где может находиться искомый элемент.
+/// Когда использовать:
+///  * массив отсортирован;
+///  * для сравнения с бинарным поиском: делений меньше, но сравнений больше.
+///
+/// Характеристики:
+///  * Сложность по времени: O(log₃ n) шагов, до 4 сравнений на шаг.
+///  * Сложность по памяти: O(1).
+///  * Требование: массив должен быть отсортирован.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_search::ternary_search;
+///
+/// let arr = [1, 2, 3, 4, 5];
+/// assert_eq!(ternary_search(&arr, &3), Some(2));
+/// ```
+///
+/// ```
+/// use exa_search::ternary_search;
+///
+/// let arr = [10, 20, 30, 40, 50, 60, 70];
+/// assert_eq!(ternary_search(&arr, &70), Some(6));
+/// assert_eq!(ternary_search(&arr, &5), None);
+/// ```
+pub fn ternary_search<T: Ord>(arr: &[T], target: &T) -> Option<usize> {
+    let mut left = 0;
+    let mut right = arr.len();
+
+    while left < right {
+        let third = (right - left) / 3;
+        let mid1 = left + third;
+        let mid2 = right - 1 - third;
+
+        match arr[mid1].cmp(target) {
+            Ordering::Equal => return Some(mid1),
+            Ordering::Greater => {
+                right = mid1;
+                continue;
+            }
+            Ordering::Less => {}
+        }
+        match arr[mid2].cmp(target) {
+            Ordering::Equal => return Some(mid2),
+            Ordering::Less => left = mid2 + 1,
+            Ordering::Greater => {
+                left = mid1 + 1;
+                right = mid2;
+            }
+        }
+    }
+
+    None
+}
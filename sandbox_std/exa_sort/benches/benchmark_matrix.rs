@@ -0,0 +1,209 @@
+//! Матрица сравнения сортировок: размер × распределение × тип элемента.
+//!
+//! Для каждой пары (тип, распределение) создаётся группа `тип/распределение`, внутри неё
+//! замеры `алгоритм/размер` с пропускной способностью в элементах в секунду.
+//! Квадратичные сортировки замеряются только до [`QUADRATIC_MAX`] элементов.
+//!
+//! Полная матрица выполняется долго, поэтому обычно запускают её часть:
+//!
+//! ```bash
+//! cargo bench -p exa_sort --bench benchmark_matrix -- "u64/random"
+//! cargo bench -p exa_sort --bench benchmark_matrix -- "nearly_sorted/.*/1000$"
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{BatchSize, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use exa_sort::{
+    MergeMode, MergeSorter, binary_insertion_sort, dual_pivot_quick_sort, heap_sort,
+    insertion_sort, intro_sort, merge_sort, parallel_merge_sort, parallel_quick_sort,
+    radix_sort_lsd, radix_sort_msd, shell_sort, three_way_quick_sort,
+};
+use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+/// Размеры входа.
+const SIZES: [usize; 6] = [10, 100, 1_000, 10_000, 100_000, 1_000_000];
+/// Наибольший размер входа для квадратичных сортировок.
+const QUADRATIC_MAX: usize = 10_000;
+/// Зерно генератора: входы одинаковы от запуска к запуску.
+const SEED: u64 = 0x5eed;
+
+/// Распределение ключей.
+#[derive(Clone, Copy)]
+enum Distribution {
+    Random,
+    Sorted,
+    Reversed,
+    /// Восемь различных значений.
+    FewUnique,
+    /// Отсортированный вход, в котором переставлен 1% пар.
+    NearlySorted,
+}
+
+impl Distribution {
+    const ALL: [Self; 5] = [
+        Self::Random,
+        Self::Sorted,
+        Self::Reversed,
+        Self::FewUnique,
+        Self::NearlySorted,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Random => "random",
+            Self::Sorted => "sorted",
+            Self::Reversed => "reversed",
+            Self::FewUnique => "few_unique",
+            Self::NearlySorted => "nearly_sorted",
+        }
+    }
+
+    /// Ключи в диапазоне `0..2^32`, чтобы все типы элементов упорядочивались одинаково.
+    fn keys(self, len: usize, rng: &mut StdRng) -> Vec<u64> {
+        let mut keys: Vec<u64> = match self {
+            Self::Random => (0..len).map(|_| rng.random_range(0..1 << 32)).collect(),
+            Self::FewUnique => (0..len).map(|_| rng.random_range(0..8)).collect(),
+            Self::Sorted | Self::NearlySorted => (0..len as u64).collect(),
+            Self::Reversed => (0..len as u64).rev().collect(),
+        };
+        if matches!(self, Self::NearlySorted) {
+            for _ in 0..len / 100 {
+                keys.swap(rng.random_range(0..len), rng.random_range(0..len));
+            }
+        }
+        keys
+    }
+}
+
+/// Тип элемента в матрице.
+trait Element: Ord + Clone + Send {
+    const NAME: &'static str;
+
+    fn from_key(key: u64) -> Self;
+}
+
+impl Element for u32 {
+    const NAME: &'static str = "u32";
+
+    fn from_key(key: u64) -> Self {
+        key as u32
+    }
+}
+
+impl Element for u64 {
+    const NAME: &'static str = "u64";
+
+    fn from_key(key: u64) -> Self {
+        key
+    }
+}
+
+impl Element for String {
+    const NAME: &'static str = "string";
+
+    fn from_key(key: u64) -> Self {
+        // Дополнение нулями сохраняет числовой порядок.
+        format!("{key:010}")
+    }
+}
+
+/// Запись размером 64 байта: ключ и полезная нагрузка.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Record {
+    key: u64,
+    payload: [u8; 56],
+}
+
+const _: () = assert!(size_of::<Record>() == 64);
+
+impl Element for Record {
+    const NAME: &'static str = "record64";
+
+    fn from_key(key: u64) -> Self {
+        Self {
+            key,
+            payload: [key as u8; 56],
+        }
+    }
+}
+
+/// Сортировка в матрице: имя, функция и признак квадратичной сложности.
+type Algorithm<T> = (&'static str, fn(&mut [T]), bool);
+
+fn algorithms<T: Element>() -> Vec<Algorithm<T>> {
+    vec![
+        ("insertion_sort", insertion_sort, true),
+        ("binary_insertion_sort", binary_insertion_sort, true),
+        ("shell_sort", shell_sort, false),
+        ("merge_sort", merge_sort, false),
+        (
+            "natural_merge_sort",
+            |arr| MergeSorter::new(MergeMode::Natural).sort(arr),
+            false,
+        ),
+        ("heap_sort", heap_sort, false),
+        ("intro_sort", intro_sort, false),
+        ("three_way_quick_sort", three_way_quick_sort, false),
+        ("dual_pivot_quick_sort", dual_pivot_quick_sort, false),
+        ("parallel_merge_sort", parallel_merge_sort, false),
+        ("parallel_quick_sort", parallel_quick_sort, false),
+        ("std_sort", <[T]>::sort, false),
+        ("std_sort_unstable", <[T]>::sort_unstable, false),
+    ]
+}
+
+/// Замеряет все сортировки и `extra` на всех распределениях и размерах для типа `T`.
+fn bench_element<T: Element>(cri: &mut Criterion, extra: &[Algorithm<T>]) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    for distribution in Distribution::ALL {
+        let mut group = cri.benchmark_group(format!("{}/{}", T::NAME, distribution.name()));
+        group.sample_size(10);
+
+        for size in SIZES {
+            let input: Vec<T> = distribution
+                .keys(size, &mut rng)
+                .into_iter()
+                .map(T::from_key)
+                .collect();
+            let batch = if size <= QUADRATIC_MAX {
+                BatchSize::SmallInput
+            } else {
+                BatchSize::LargeInput
+            };
+            group.throughput(Throughput::Elements(size as u64));
+
+            for &(name, sort, quadratic) in algorithms::<T>().iter().chain(extra) {
+                if quadratic && size > QUADRATIC_MAX {
+                    continue;
+                }
+                group.bench_with_input(BenchmarkId::new(name, size), &input, |b, input| {
+                    b.iter_batched_ref(|| input.clone(), |arr| sort(black_box(arr)), batch)
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+fn benchmark_matrix(cri: &mut Criterion) {
+    bench_element::<u32>(
+        cri,
+        &[
+            ("radix_sort_lsd", radix_sort_lsd, false),
+            ("radix_sort_msd", radix_sort_msd, false),
+        ],
+    );
+    bench_element::<u64>(
+        cri,
+        &[
+            ("radix_sort_lsd", radix_sort_lsd, false),
+            ("radix_sort_msd", radix_sort_msd, false),
+        ],
+    );
+    bench_element::<String>(cri, &[]);
+    bench_element::<Record>(cri, &[]);
+}
+
+criterion_group!(benches, benchmark_matrix);
+criterion_main!(benches);
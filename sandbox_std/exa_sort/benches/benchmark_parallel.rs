@@ -0,0 +1,86 @@
+//! Сравнение параллельных сортировок с последовательными и `slice::sort`.
+//!
+//! Как запустить:
+//!
+//! ```bash
+//! cargo bench -p exa_sort --bench benchmark_parallel
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use exa_sort::{ParallelConfig, intro_sort, merge_sort, parallel_merge_sort, parallel_quick_sort};
+
+/// Размер массива для тестирования
+const ARR_SIZE: usize = 1_000_000;
+
+fn benchmark_parallel(cri: &mut Criterion) {
+    let arr: Vec<u64> = (0..ARR_SIZE).map(|_| rand::random()).collect();
+
+    let mut group = cri.benchmark_group("parallel");
+    group.sample_size(10);
+
+    group.bench_function("merge_sort", |b| {
+        b.iter_batched_ref(
+            || arr.clone(),
+            |arr| merge_sort(black_box(arr)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("parallel_merge_sort", |b| {
+        b.iter_batched_ref(
+            || arr.clone(),
+            |arr| parallel_merge_sort(black_box(arr)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("intro_sort", |b| {
+        b.iter_batched_ref(
+            || arr.clone(),
+            |arr| intro_sort(black_box(arr)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("parallel_quick_sort", |b| {
+        b.iter_batched_ref(
+            || arr.clone(),
+            |arr| parallel_quick_sort(black_box(arr)),
+            BatchSize::LargeInput,
+        )
+    });
+    for threads in [2, 4, 8] {
+        let config = ParallelConfig {
+            threads,
+            ..Default::default()
+        };
+        group.bench_function(format!("parallel_merge_sort_{threads}"), |b| {
+            b.iter_batched_ref(
+                || arr.clone(),
+                |arr| config.merge_sort_by(black_box(arr), u64::cmp),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_function(format!("parallel_quick_sort_{threads}"), |b| {
+            b.iter_batched_ref(
+                || arr.clone(),
+                |arr| config.quick_sort_by(black_box(arr), u64::cmp),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.bench_function("base", |b| {
+        b.iter_batched_ref(|| arr.clone(), |arr| arr.sort(), BatchSize::LargeInput)
+    });
+    group.bench_function("base_unstable", |b| {
+        b.iter_batched_ref(
+            || arr.clone(),
+            |arr| arr.sort_unstable(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_parallel);
+criterion_main!(benches);
@@ -1,7 +1,15 @@
+// Исходные замеры копируют массивы через `clone()`.
+#![allow(clippy::clone_on_copy)]
+
 use std::hint::black_box;
 
 use criterion::{Criterion, criterion_group, criterion_main};
-use exa_sort::{bubble_sort, heap_sort, insertion_sort, merge_sort, quick_sort, selection_sort};
+use exa_sort::{
+    binary_insertion_sort, bubble_sort, bucket_sort, cocktail_shaker_sort, comb_sort,
+    counting_sort, dual_pivot_quick_sort, gnome_sort, heap_sort, insertion_sort, intro_sort,
+    merge_sort, quick_sort, radix_sort_lsd, radix_sort_msd, selection_sort, shell_sort, sort_small,
+    three_way_quick_sort,
+};
 
 /// Малый массив для тестирования сортировки
 const SMALL_ARR: [i32; 5] = [5, 2, 8, 1, 9];
@@ -58,6 +66,71 @@ fn benchmark_sort(cri: &mut Criterion) {
         })
     });
 
+    cri.bench_function("shell_sort_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR;
+            shell_sort(black_box(&mut arr));
+        })
+    });
+    cri.bench_function("shell_sort_large", |b| {
+        b.iter(|| {
+            let mut arr = BIG_ARR;
+            shell_sort(black_box(&mut arr));
+        })
+    });
+
+    cri.bench_function("comb_sort_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR;
+            comb_sort(black_box(&mut arr));
+        })
+    });
+    cri.bench_function("comb_sort_large", |b| {
+        b.iter(|| {
+            let mut arr = BIG_ARR;
+            comb_sort(black_box(&mut arr));
+        })
+    });
+
+    cri.bench_function("cocktail_shaker_sort_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR;
+            cocktail_shaker_sort(black_box(&mut arr));
+        })
+    });
+    cri.bench_function("cocktail_shaker_sort_large", |b| {
+        b.iter(|| {
+            let mut arr = BIG_ARR;
+            cocktail_shaker_sort(black_box(&mut arr));
+        })
+    });
+
+    cri.bench_function("gnome_sort_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR;
+            gnome_sort(black_box(&mut arr));
+        })
+    });
+    cri.bench_function("gnome_sort_large", |b| {
+        b.iter(|| {
+            let mut arr = BIG_ARR;
+            gnome_sort(black_box(&mut arr));
+        })
+    });
+
+    cri.bench_function("binary_insertion_sort_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR;
+            binary_insertion_sort(black_box(&mut arr));
+        })
+    });
+    cri.bench_function("binary_insertion_sort_large", |b| {
+        b.iter(|| {
+            let mut arr = BIG_ARR;
+            binary_insertion_sort(black_box(&mut arr));
+        })
+    });
+
     cri.bench_function("quick_sort_small", |b| {
         b.iter(|| {
             let mut arr = SMALL_ARR.clone();
@@ -71,6 +144,32 @@ fn benchmark_sort(cri: &mut Criterion) {
         })
     });
 
+    cri.bench_function("three_way_quick_sort_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR;
+            three_way_quick_sort(black_box(&mut arr));
+        })
+    });
+    cri.bench_function("three_way_quick_sort_large", |b| {
+        b.iter(|| {
+            let mut arr = BIG_ARR;
+            three_way_quick_sort(black_box(&mut arr));
+        })
+    });
+
+    cri.bench_function("dual_pivot_quick_sort_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR;
+            dual_pivot_quick_sort(black_box(&mut arr));
+        })
+    });
+    cri.bench_function("dual_pivot_quick_sort_large", |b| {
+        b.iter(|| {
+            let mut arr = BIG_ARR;
+            dual_pivot_quick_sort(black_box(&mut arr));
+        })
+    });
+
     cri.bench_function("merge_sort_small", |b| {
         b.iter(|| {
             let mut arr = SMALL_ARR.clone();
@@ -97,6 +196,71 @@ fn benchmark_sort(cri: &mut Criterion) {
         })
     });
 
+    cri.bench_function("intro_sort_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR;
+            intro_sort(black_box(&mut arr));
+        })
+    });
+    cri.bench_function("intro_sort_large", |b| {
+        b.iter(|| {
+            let mut arr = BIG_ARR;
+            intro_sort(black_box(&mut arr));
+        })
+    });
+
+    cri.bench_function("counting_sort_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR;
+            counting_sort(black_box(&mut arr));
+        })
+    });
+    cri.bench_function("counting_sort_large", |b| {
+        b.iter(|| {
+            let mut arr = BIG_ARR;
+            counting_sort(black_box(&mut arr));
+        })
+    });
+
+    cri.bench_function("radix_sort_lsd_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR;
+            radix_sort_lsd(black_box(&mut arr));
+        })
+    });
+    cri.bench_function("radix_sort_lsd_large", |b| {
+        b.iter(|| {
+            let mut arr = BIG_ARR;
+            radix_sort_lsd(black_box(&mut arr));
+        })
+    });
+
+    cri.bench_function("radix_sort_msd_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR;
+            radix_sort_msd(black_box(&mut arr));
+        })
+    });
+    cri.bench_function("radix_sort_msd_large", |b| {
+        b.iter(|| {
+            let mut arr = BIG_ARR;
+            radix_sort_msd(black_box(&mut arr));
+        })
+    });
+
+    cri.bench_function("bucket_sort_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR.map(f64::from);
+            bucket_sort(black_box(&mut arr), 0.0..10.0);
+        })
+    });
+    cri.bench_function("bucket_sort_large", |b| {
+        b.iter(|| {
+            let mut arr = BIG_ARR.map(f64::from);
+            bucket_sort(black_box(&mut arr), 0.0..100.0);
+        })
+    });
+
     cri.bench_function("base_small", |b| {
         b.iter(|| {
             let mut arr = SMALL_ARR.clone();
@@ -109,6 +273,13 @@ fn benchmark_sort(cri: &mut Criterion) {
             arr.sort();
         })
     });
+
+    cri.bench_function("sort_small_small", |b| {
+        b.iter(|| {
+            let mut arr = SMALL_ARR;
+            sort_small(black_box(&mut arr));
+        })
+    });
 }
 
 criterion_group!(benches, benchmark_sort);
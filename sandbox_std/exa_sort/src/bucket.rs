@@ -0,0 +1,112 @@
+//! Блочная сортировка (Bucket Sort).
+//!
+//! Диапазон значений делится на `n` равных блоков, элементы раскладываются по блокам,
+//! каждый блок сортируется отдельно, и блоки записываются по порядку. Для равномерно
+//! распределённых данных в каждом блоке в среднем один элемент, и сложность — O(n).
+
+use std::ops::Range;
+
+use crate::insertion_sort_by;
+
+/// Блочная сортировка (Bucket Sort) для чисел с плавающей точкой из диапазона `range`.
+/// Значения за пределами диапазона попадают в крайние блоки и тоже сортируются верно,
+/// но чем точнее диапазон, тем быстрее сортировка. Порядок — [`f64::total_cmp`],
+/// NaN со знаковым битом помещается в первый блок, остальные NaN — в последний.
+///
+/// # Паника
+///
+/// Если диапазон пуст или перевёрнут (`range.start >= range.end`), а также если
+/// одна из границ — NaN.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::bucket_sort;
+///
+/// let mut arr = [0.5, 0.2, 0.8, 0.1, 0.9];
+/// bucket_sort(&mut arr, 0.0..1.0);
+/// assert_eq!(arr, [0.1, 0.2, 0.5, 0.8, 0.9]);
+/// ```
+///
+/// ```
+/// use exa_sort::bucket_sort;
+///
+/// let mut arr = [10.5f32, -3.0, 7.25, 100.0, 0.0];
+/// bucket_sort(&mut arr, -5.0..15.0);
+/// assert_eq!(arr, [-3.0, 0.0, 7.25, 10.5, 100.0]);
+/// ```
+pub fn bucket_sort<T>(arr: &mut [T], range: Range<f64>)
+where
+    T: Copy + Into<f64>,
+{
+    assert!(
+        range.start < range.end,
+        "пустой диапазон значений: {}..{}",
+        range.start,
+        range.end
+    );
+    let len = arr.len();
+    if len <= 1 {
+        return;
+    }
+
+    let scale = len as f64 / (range.end - range.start);
+    let mut buckets: Vec<Vec<T>> = vec![Vec::new(); len];
+    for &value in arr.iter() {
+        let value_f64 = value.into();
+        let position = (value_f64 - range.start) * scale;
+        // Отрицательные значения при приведении к usize становятся 0.
+        let index = if position.is_nan() {
+            // В порядке `total_cmp` отрицательный NaN меньше −∞, положительный больше +∞.
+            if value_f64.is_sign_negative() {
+                0
+            } else {
+                len - 1
+            }
+        } else {
+            (position as usize).min(len - 1)
+        };
+        buckets[index].push(value);
+    }
+
+    let mut i = 0;
+    for mut bucket in buckets {
+        insertion_sort_by(&mut bucket, |a, b| (*a).into().total_cmp(&(*b).into()));
+        arr[i..i + bucket.len()].copy_from_slice(&bucket);
+        i += bucket.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_sort() {
+        let mut arr: Vec<f64> = (0..10_000u64)
+            .map(|i| (i.wrapping_mul(2_654_435_761) % 1_000_003) as f64 / 1_000_003.0)
+            .collect();
+        arr.extend([
+            -1.0,
+            2.0,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            0.5,
+            0.5,
+            -f64::NAN,
+            f64::NAN,
+        ]);
+        let mut expected = arr.clone();
+        expected.sort_by(f64::total_cmp);
+        bucket_sort(&mut arr, 0.0..1.0);
+        // NaN не равен сам себе, поэтому сравниваем битовые представления.
+        let bits = |values: &[f64]| values.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+        assert_eq!(bits(&arr), bits(&expected));
+    }
+
+    #[test]
+    #[should_panic(expected = "пустой диапазон")]
+    fn test_reversed_range() {
+        bucket_sort(&mut [0.5, 0.2], 1.0..0.0);
+    }
+}
@@ -0,0 +1,145 @@
+//! Сортировка подсчётом (Counting Sort).
+//!
+//! Для каждого ключа считается количество элементов, после чего каждому элементу
+//! вычисляется итоговая позиция. Сложность O(n + k), где `k` — диапазон ключей,
+//! поэтому подходит только для небольших диапазонов: оценки, возраст, коды символов.
+
+use crate::radix::RadixKey;
+
+/// Максимальный диапазон значений для [`counting_sort`].
+pub const MAX_COUNTING_RANGE: usize = 1 << 24;
+
+/// Сортировка подсчётом (Counting Sort)
+/// Сложность O(n + k), где `k = max - min + 1`. Стабильна.
+///
+/// # Паника
+///
+/// Если диапазон значений больше [`MAX_COUNTING_RANGE`].
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::counting_sort;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// counting_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_sort::counting_sort;
+///
+/// let mut arr = [-3i8, 3, 0, -3, 2, -1];
+/// counting_sort(&mut arr);
+/// assert_eq!(arr, [-3, -3, -1, 0, 2, 3]);
+/// ```
+pub fn counting_sort<T: RadixKey>(arr: &mut [T]) {
+    let Some(min) = arr.iter().map(|v| v.key()).min() else {
+        return;
+    };
+    let max = arr.iter().map(|v| v.key()).max().unwrap_or(min);
+    assert!(
+        max - min < MAX_COUNTING_RANGE as u128,
+        "диапазон значений слишком велик для сортировки подсчётом"
+    );
+
+    counting_sort_by_key(arr, |v| (v.key() - min) as usize);
+}
+
+/// Стабильная сортировка подсчётом по целочисленному ключу.
+/// Элементы переставляются на месте, поэтому `T` может быть любым.
+///
+/// Память O(n + max_key): таблица счётчиков занимает `max_key + 1` слов независимо
+/// от количества элементов, так что ключ порядка миллиарда потребует гигабайты.
+/// Для широких диапазонов подходят [`crate::radix_sort_lsd`] или сортировки сравнением.
+///
+/// # Паника
+///
+/// Если ключ равен `usize::MAX` или таблицу счётчиков не удаётся выделить.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::counting_sort_by_key;
+///
+/// let mut arr = [("Оля", 5), ("Петя", 3), ("Вася", 5), ("Аня", 4)];
+/// counting_sort_by_key(&mut arr, |&(_, grade)| grade);
+/// assert_eq!(arr, [("Петя", 3), ("Аня", 4), ("Оля", 5), ("Вася", 5)]);
+/// ```
+pub fn counting_sort_by_key<T, F>(arr: &mut [T], key: F)
+where
+    F: FnMut(&T) -> usize,
+{
+    let keys: Vec<usize> = arr.iter().map(key).collect();
+    let Some(&max) = keys.iter().max() else {
+        return;
+    };
+
+    let size = max
+        .checked_add(1)
+        .expect("ключ usize::MAX не помещается в таблицу счётчиков");
+    let mut offsets = vec![0usize; size];
+    for &key in &keys {
+        offsets[key] += 1;
+    }
+    let mut sum = 0;
+    for offset in &mut offsets {
+        let count = *offset;
+        *offset = sum;
+        sum += count;
+    }
+
+    // Итоговая позиция каждого элемента.
+    let mut dest = keys;
+    for key in &mut dest {
+        let position = offsets[*key];
+        offsets[*key] += 1;
+        *key = position;
+    }
+
+    // Применяем перестановку циклами: каждый обмен ставит один элемент на место.
+    for i in 0..arr.len() {
+        while dest[i] != i {
+            let j = dest[i];
+            arr.swap(i, j);
+            dest.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counting_sort() {
+        let mut arr: Vec<i16> = (0..10_000)
+            .map(|i| (i * 7919 % 2001 - 1000) as i16)
+            .collect();
+        let mut expected = arr.clone();
+        expected.sort();
+        counting_sort(&mut arr);
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_stable() {
+        let mut arr: Vec<(usize, usize)> = (0..10_000).map(|i| (i * 7919 % 97, i)).collect();
+        let mut expected = arr.clone();
+        expected.sort_by_key(|&(key, _)| key);
+        counting_sort_by_key(&mut arr, |&(key, _)| key);
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "usize::MAX")]
+    fn test_max_key() {
+        counting_sort_by_key(&mut [1, 2], |_| usize::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_too_large() {
+        counting_sort(&mut [0u64, u64::MAX]);
+    }
+}
@@ -0,0 +1,267 @@
+//! Быстрая сортировка с двумя опорными элементами (Dual-Pivot Quick Sort Ярославского).
+//!
+//! Срез делится двумя опорными элементами `p1 <= p2` на три части:
+//! `< p1`, `p1..=p2` и `> p2`. Каждая итерация разбиения отсекает больше элементов,
+//! чем классическое разбиение. При `p1 == p2` средняя часть состоит из равных
+//! элементов и в рекурсию не попадает, а из слишком большой средней части копии
+//! `p1` и `p2` переносятся к её краям. Поэтому массивы с повторами сортируются
+//! за O(n log n).
+
+use std::{cmp::Ordering, mem};
+
+use crate::instrument::{Collector, swap};
+
+/// Быстрая сортировка с двумя опорными элементами (Dual-Pivot Quick Sort)
+/// Средняя сложность O(n log n), на практике делает меньше обращений к памяти,
+/// чем классическая быстрая сортировка. Сортировка нестабильна.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::dual_pivot_quick_sort;
+///
+/// let mut arr = [2, 3, 1];
+/// dual_pivot_quick_sort(&mut arr);
+/// assert_eq!(arr, [1, 2 ,3]);
+/// ```
+///
+/// ```
+/// use exa_sort::dual_pivot_quick_sort;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// dual_pivot_quick_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_sort::dual_pivot_quick_sort;
+///
+/// let mut arr = [10, 4, 7, 3, 1, 8, 2, 9, 5, 6, 15, 12, 18, 11, 14, 17, 13, 16, 19, 1];
+/// dual_pivot_quick_sort(&mut arr);
+/// assert_eq!(arr, [1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
+/// ```
+pub fn dual_pivot_quick_sort<T: Ord>(arr: &mut [T]) {
+    dual_pivot_quick_sort_by(arr, T::cmp);
+}
+
+/// Быстрая сортировка с двумя опорными элементами и пользовательской функцией сравнения.
+/// Аналог [`dual_pivot_quick_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::dual_pivot_quick_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// dual_pivot_quick_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn dual_pivot_quick_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    dual_pivot_quick_sort_with(arr, compare, &mut ());
+}
+
+/// Быстрая сортировка с двумя опорными элементами по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::dual_pivot_quick_sort_by_key;
+///
+/// let mut arr = ["ccc", "a", "bb"];
+/// dual_pivot_quick_sort_by_key(&mut arr, |a| a.len());
+/// assert_eq!(arr, ["a", "bb", "ccc"]);
+/// ```
+pub fn dual_pivot_quick_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    dual_pivot_quick_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn dual_pivot_quick_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    dual_pivot_helper(arr, &mut compare, collector);
+}
+
+fn dual_pivot_helper<T, F, C>(mut arr: &mut [T], compare: &mut F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    while arr.len() > 1 {
+        let len = arr.len();
+        let (lt, gt, pivots_equal) = dual_pivot_partition(arr, compare, collector);
+
+        // Границы средней части `arr[start..end]`, которую ещё нужно сортировать.
+        let (start, end) = if pivots_equal {
+            // При равных опорных элементах средняя часть уже отсортирована.
+            (gt, gt)
+        } else if (gt - lt - 1) * 7 > len * 4 {
+            // Слишком большая средняя часть обычно полна копий опорных элементов.
+            gather_pivots(arr, lt, gt, compare, collector)
+        } else {
+            (lt + 1, gt)
+        };
+
+        let (head, right) = mem::take(&mut arr).split_at_mut(gt + 1);
+        let (left, head) = head.split_at_mut(lt);
+        let middle = &mut head[start - lt..end - lt];
+
+        // Рекурсивно сортируем две меньшие части, самую большую обрабатываем в цикле.
+        let mut parts = [left, middle, right];
+        parts.sort_by_key(|part| part.len());
+        let [small, medium, large] = parts;
+        dual_pivot_helper(small, compare, collector);
+        dual_pivot_helper(medium, compare, collector);
+        arr = large;
+    }
+}
+
+/// Разбиение относительно опорных элементов `p1 = arr[0]` и `p2 = arr[len - 1]`.
+/// Возвращает `(lt, gt, p1 == p2)`, где `lt` и `gt` — итоговые позиции опорных элементов:
+/// `arr[..lt] < p1`, `p1 <= arr[lt + 1..gt] <= p2`, `arr[gt + 1..] > p2`.
+fn dual_pivot_partition<T, F, C>(
+    arr: &mut [T],
+    compare: &mut F,
+    collector: &mut C,
+) -> (usize, usize, bool)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    let len = arr.len();
+    let last = len - 1;
+
+    // Опорные элементы берём из первой и последней трети, чтобы не деградировать
+    // на уже отсортированных массивах.
+    if len > 3 {
+        swap(arr, 0, len / 3, collector);
+        swap(arr, last, last - len / 3, collector);
+    }
+    let pivots_equal = match compare(&arr[0], &arr[last]) {
+        Ordering::Greater => {
+            swap(arr, 0, last, collector);
+            false
+        }
+        ordering => ordering == Ordering::Equal,
+    };
+
+    let mut lt = 1;
+    let mut i = 1;
+    let mut gt = last;
+
+    while i < gt {
+        if compare(&arr[i], &arr[0]) == Ordering::Less {
+            swap(arr, i, lt, collector);
+            lt += 1;
+            i += 1;
+        } else if compare(&arr[i], &arr[last]) == Ordering::Greater {
+            gt -= 1;
+            swap(arr, i, gt, collector);
+        } else {
+            i += 1;
+        }
+    }
+
+    lt -= 1;
+    swap(arr, 0, lt, collector);
+    swap(arr, last, gt, collector);
+
+    (lt, gt, pivots_equal)
+}
+
+/// Собирает копии опорных элементов из средней части `arr[lt + 1..gt]`: равные
+/// `p1 = arr[lt]` переносятся в её начало, равные `p2 = arr[gt]` — в конец, как в
+/// сортировке JDK. Возвращает границы `(start, end)` оставшейся средней части,
+/// в которой `p1 < arr[start..end] < p2`. Без этого массив из двух различных значений
+/// сортируется за квадратичное время: средняя часть каждый раз теряет только
+/// два опорных элемента.
+fn gather_pivots<T, F, C>(
+    arr: &mut [T],
+    lt: usize,
+    gt: usize,
+    compare: &mut F,
+    collector: &mut C,
+) -> (usize, usize)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    let mut start = lt + 1;
+    let mut i = start;
+    let mut end = gt;
+
+    while i < end {
+        if compare(&arr[i], &arr[lt]) == Ordering::Equal {
+            swap(arr, i, start, collector);
+            start += 1;
+            i += 1;
+        } else if compare(&arr[i], &arr[gt]) == Ordering::Equal {
+            end -= 1;
+            swap(arr, i, end, collector);
+        } else {
+            i += 1;
+        }
+    }
+
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition() {
+        let mut arr = [2, 5, 1, 4, 6, 3, 7, 0, 5];
+        let (lt, gt, pivots_equal) = dual_pivot_partition(&mut arr, &mut i32::cmp, &mut ());
+        assert!(!pivots_equal);
+        assert!(arr[..lt].iter().all(|v| *v < arr[lt]));
+        assert!(
+            arr[lt + 1..gt]
+                .iter()
+                .all(|v| arr[lt] <= *v && *v <= arr[gt])
+        );
+        assert!(arr[gt + 1..].iter().all(|v| *v > arr[gt]));
+    }
+
+    #[test]
+    fn test_sorted_and_duplicates() {
+        let mut sorted: Vec<u32> = (0..100_000).collect();
+        let expected = sorted.clone();
+        dual_pivot_quick_sort(&mut sorted);
+        assert_eq!(sorted, expected);
+
+        let mut arr: Vec<u32> = (0..100_000).map(|i| i % 100).collect();
+        let mut expected = arr.clone();
+        expected.sort();
+        dual_pivot_quick_sort(&mut arr);
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_few_distinct() {
+        // Массив из нескольких различных значений сортируется за O(n log n) сравнений.
+        let len: usize = 40_000;
+        for distinct in [2, 3, 5] {
+            let mut arr: Vec<usize> = (0..len).map(|i| i % distinct).collect();
+            let mut comparisons = 0;
+            dual_pivot_quick_sort_by(&mut arr, |a, b| {
+                comparisons += 1;
+                a.cmp(b)
+            });
+            assert!(arr.is_sorted(), "distinct = {distinct}");
+            assert!(
+                comparisons < 2 * len * len.ilog2() as usize,
+                "distinct = {distinct}: {comparisons} сравнений"
+            );
+        }
+    }
+}
@@ -0,0 +1,397 @@
+//! Внешняя сортировка слиянием для данных, не помещающихся в память.
+//!
+//! Вход читается частями не больше [`ExternalSorter::memory`] байт. Каждая часть сортируется
+//! в памяти ([`crate::merge_sort`]) и записывается во временный файл — серию. Затем серии
+//! сливаются k-путевым слиянием на [`BinaryHeap`]: в куче лежит по одной текущей записи
+//! из каждой серии. Если серий больше [`MAX_FAN_IN`], они сливаются в несколько проходов,
+//! чтобы не держать открытыми слишком много файлов.
+//!
+//! Записи сравниваются побайтово, как `sort` при `LC_ALL=C`. Временные файлы удаляются
+//! и при ошибке.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, binary_heap::PeekMut},
+    env,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    mem,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::merge_sort;
+
+/// Объём памяти под записи по умолчанию: 64 МиБ.
+pub const DEFAULT_MEMORY: usize = 64 << 20;
+
+/// Максимальное количество серий, сливаемых за один проход.
+pub const MAX_FAN_IN: usize = 64;
+
+/// Счётчик для уникальных имён временных файлов внутри процесса.
+static RUN_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Формат записей во входном файле.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// Строки, разделённые `\n`. В выходе каждая строка завершается `\n`,
+    /// даже если последняя строка входа его не имела.
+    Lines,
+    /// Записи фиксированной длины в байтах. Длина должна быть больше нуля.
+    Fixed(usize),
+}
+
+impl RecordFormat {
+    /// Читает следующую запись в `record`. Возвращает `false`, если вход закончился.
+    fn read<R: BufRead>(self, input: &mut R, record: &mut Vec<u8>) -> io::Result<bool> {
+        record.clear();
+        match self {
+            RecordFormat::Lines => {
+                if input.read_until(b'\n', record)? == 0 {
+                    return Ok(false);
+                }
+                if record.last() == Some(&b'\n') {
+                    record.pop();
+                }
+                Ok(true)
+            }
+            RecordFormat::Fixed(size) => {
+                let read = input.take(size as u64).read_to_end(record)?;
+                match read {
+                    0 => Ok(false),
+                    read if read == size => Ok(true),
+                    read => Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("неполная запись: {read} байт из {size}"),
+                    )),
+                }
+            }
+        }
+    }
+
+    fn write<W: Write>(self, output: &mut W, record: &[u8]) -> io::Result<()> {
+        output.write_all(record)?;
+        if self == RecordFormat::Lines {
+            output.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// Внешняя сортировка.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::{ExternalSorter, RecordFormat};
+///
+/// let input = "груша\nяблоко\nабрикос\nвишня";
+/// let mut output = Vec::new();
+/// ExternalSorter::new(RecordFormat::Lines, 16)
+///     .sort(input.as_bytes(), &mut output)
+///     .unwrap();
+/// assert_eq!(output, "абрикос\nвишня\nгруша\nяблоко\n".as_bytes());
+/// ```
+///
+/// ```
+/// use exa_sort::{ExternalSorter, RecordFormat};
+///
+/// let input = b"05ab02cd08ef01gh09ij";
+/// let mut output = Vec::new();
+/// ExternalSorter::new(RecordFormat::Fixed(4), 8)
+///     .sort(&input[..], &mut output)
+///     .unwrap();
+/// assert_eq!(output, b"01gh02cd05ab08ef09ij");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalSorter {
+    /// Формат записей.
+    pub format: RecordFormat,
+    /// Сколько байт записей держать в памяти при формировании серии.
+    /// Серия содержит хотя бы одну запись, даже если та больше бюджета.
+    pub memory: usize,
+    /// Каталог для временных файлов.
+    pub temp_dir: PathBuf,
+}
+
+impl ExternalSorter {
+    /// Создаёт сортировку с временными файлами в [`env::temp_dir`].
+    pub fn new(format: RecordFormat, memory: usize) -> Self {
+        Self {
+            format,
+            memory,
+            temp_dir: env::temp_dir(),
+        }
+    }
+
+    /// Сортирует файл `input` и записывает результат в `output`.
+    /// `output` может совпадать с `input`: он создаётся после чтения всего входа.
+    pub fn sort_file(&self, input: &Path, output: &Path) -> io::Result<()> {
+        let runs = self.split(BufReader::new(File::open(input)?))?;
+        let output = BufWriter::new(File::create(output)?);
+        self.finish(runs, output)
+    }
+
+    /// Сортирует записи из `input` и записывает результат в `output`.
+    pub fn sort<R: Read, W: Write>(&self, input: R, output: W) -> io::Result<()> {
+        let runs = self.split(BufReader::new(input))?;
+        self.finish(runs, output)
+    }
+
+    /// Разбивает вход на отсортированные серии.
+    fn split<R: BufRead>(&self, mut input: R) -> io::Result<Split> {
+        if self.format == RecordFormat::Fixed(0) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "длина записи должна быть больше нуля",
+            ));
+        }
+        let mut runs = Vec::new();
+        let mut chunk = Vec::new();
+        let mut used = 0;
+        let mut record = Vec::new();
+        while self.format.read(&mut input, &mut record)? {
+            let cost = record.len() + mem::size_of::<Vec<u8>>();
+            if !chunk.is_empty() && used + cost > self.memory {
+                runs.push(self.write_run(&mut chunk)?);
+                used = 0;
+            }
+            used += cost;
+            chunk.push(mem::take(&mut record));
+        }
+
+        if runs.is_empty() {
+            // Вход поместился в память: временные файлы не нужны.
+            merge_sort(&mut chunk);
+            return Ok(Split::Memory(chunk));
+        }
+        if !chunk.is_empty() {
+            runs.push(self.write_run(&mut chunk)?);
+        }
+        Ok(Split::Runs(runs))
+    }
+
+    /// Сортирует `chunk`, записывает его в новую серию и очищает.
+    fn write_run(&self, chunk: &mut Vec<Vec<u8>>) -> io::Result<Run> {
+        merge_sort(chunk);
+        let (run, file) = Run::create(&self.temp_dir)?;
+        let mut output = BufWriter::new(file);
+        for record in chunk.drain(..) {
+            self.format.write(&mut output, &record)?;
+        }
+        output.flush()?;
+        Ok(run)
+    }
+
+    fn finish<W: Write>(&self, split: Split, mut output: W) -> io::Result<()> {
+        match split {
+            Split::Memory(chunk) => {
+                for record in &chunk {
+                    self.format.write(&mut output, record)?;
+                }
+            }
+            Split::Runs(mut runs) => {
+                while runs.len() > MAX_FAN_IN {
+                    runs = runs
+                        .chunks(MAX_FAN_IN)
+                        .map(|group| {
+                            let (run, file) = Run::create(&self.temp_dir)?;
+                            self.merge(group, BufWriter::new(file))?;
+                            Ok(run)
+                        })
+                        .collect::<io::Result<_>>()?;
+                }
+                self.merge(&runs, &mut output)?;
+            }
+        }
+        output.flush()
+    }
+
+    /// k-путевое слияние серий.
+    fn merge<W: Write>(&self, runs: &[Run], mut output: W) -> io::Result<()> {
+        let buffer = (self.memory / (runs.len() + 1)).clamp(1 << 12, 1 << 20);
+        let mut readers = runs
+            .iter()
+            .map(|run| Ok(BufReader::with_capacity(buffer, File::open(&run.path)?)))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut heap = BinaryHeap::with_capacity(readers.len());
+        for (index, reader) in readers.iter_mut().enumerate() {
+            let mut record = Vec::new();
+            if self.format.read(reader, &mut record)? {
+                heap.push(Reverse((record, index)));
+            }
+        }
+
+        while let Some(mut head) = heap.peek_mut() {
+            let Reverse((record, index)) = &mut *head;
+            self.format.write(&mut output, record)?;
+            if !self.format.read(&mut readers[*index], record)? {
+                PeekMut::pop(head);
+            }
+        }
+        output.flush()
+    }
+}
+
+impl Default for ExternalSorter {
+    fn default() -> Self {
+        Self::new(RecordFormat::Lines, DEFAULT_MEMORY)
+    }
+}
+
+/// Результат разбиения входа.
+enum Split {
+    /// Все записи поместились в память.
+    Memory(Vec<Vec<u8>>),
+    /// Серии во временных файлах, в порядке входа.
+    Runs(Vec<Run>),
+}
+
+/// Временный файл с серией. Удаляется при удалении значения.
+struct Run {
+    path: PathBuf,
+}
+
+impl Run {
+    /// Создаёт новый временный файл. Существующие файлы с тем же именем не трогает:
+    /// берёт следующий номер.
+    fn create(dir: &Path) -> io::Result<(Self, File)> {
+        loop {
+            let id = RUN_ID.fetch_add(1, Ordering::Relaxed);
+            let path = dir.join(format!("exa_sort-{}-{id}.run", process::id()));
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((Self { path }, file)),
+                Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    /// Отдельный каталог для временных файлов теста.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("exa_sort-{}-{name}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn lines(len: usize) -> Vec<String> {
+        let mut rng = StdRng::seed_from_u64(0x1234_5678);
+        (0..len)
+            .map(|_| format!("{:x}", rng.random_range(0..1u32 << 24)))
+            .collect()
+    }
+
+    #[test]
+    fn test_file_larger_than_memory() {
+        let dir = temp_dir("lines");
+        let input = dir.join("input.txt");
+        let output = dir.join("output.txt");
+
+        let mut expected = lines(20_000);
+        fs::write(&input, expected.join("\n")).unwrap();
+        expected.sort();
+
+        let mut sorter = ExternalSorter::new(RecordFormat::Lines, 1 << 14);
+        sorter.temp_dir = dir.clone();
+        assert!(fs::metadata(&input).unwrap().len() > sorter.memory as u64);
+        sorter.sort_file(&input, &output).unwrap();
+
+        let sorted = fs::read_to_string(&output).unwrap();
+        assert_eq!(sorted, expected.join("\n") + "\n");
+
+        // Остались только вход и выход.
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_multi_pass() {
+        // Бюджет меньше пары записей: серий больше MAX_FAN_IN.
+        let dir = temp_dir("multi_pass");
+        let mut sorter = ExternalSorter::new(RecordFormat::Lines, 64);
+        sorter.temp_dir = dir.clone();
+
+        let mut expected = lines(1000);
+        let mut output = Vec::new();
+        sorter
+            .sort(expected.join("\n").as_bytes(), &mut output)
+            .unwrap();
+        expected.sort();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            expected.join("\n") + "\n"
+        );
+
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fixed() {
+        // Записи из двух байт: ключ и позиция во входе.
+        let input: Vec<u8> = (0..200u8).flat_map(|i| [b'a' + i % 7, i]).collect();
+        let mut expected: Vec<&[u8]> = input.chunks(2).collect();
+        expected.sort();
+
+        let dir = temp_dir("fixed");
+        let mut sorter = ExternalSorter::new(RecordFormat::Fixed(2), 128);
+        sorter.temp_dir = dir.clone();
+
+        let mut output = Vec::new();
+        sorter.sort(&input[..], &mut output).unwrap();
+        assert_eq!(output, expected.concat());
+
+        let error = sorter.sort(&input[..3], &mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_zero_size() {
+        let error = ExternalSorter::new(RecordFormat::Fixed(0), 128)
+            .sort(&b"abc"[..], &mut Vec::new())
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_existing_file() {
+        // Чужой файл с именем следующей серии не перезаписывается и не удаляется.
+        let dir = temp_dir("existing");
+        let id = RUN_ID.load(Ordering::Relaxed);
+        let foreign = dir.join(format!("exa_sort-{}-{id}.run", process::id()));
+        fs::write(&foreign, "чужие данные").unwrap();
+
+        let (run, _) = Run::create(&dir).unwrap();
+        assert_ne!(run.path, foreign);
+        drop(run);
+        assert_eq!(fs::read_to_string(&foreign).unwrap(), "чужие данные");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut output = Vec::new();
+        ExternalSorter::default()
+            .sort(io::empty(), &mut output)
+            .unwrap();
+        assert!(output.is_empty());
+    }
+}
@@ -0,0 +1,466 @@
+//! Инструментирование сортировок: подсчёт сравнений, обменов, перемещений и выделений памяти.
+//!
+//! Сравнения считает обёртка над функцией сравнения, которую получает `*_by` вариант
+//! сортировки. Об обменах, перемещениях и выделениях памяти алгоритм сообщает сборщику
+//! (`Collector`), переданному параметром. Публичные функции сортировки передают пустой
+//! сборщик `()`: его методы ничего не делают и исчезают при компиляции, поэтому обычный
+//! вызов за инструментирование не платит.
+//!
+//! Измерить ([`SortStats::measure`]) и записать ([`crate::Trace::record`]) можно
+//! сортировки, перечисленные в [`Algorithm`]. Остальные функции крейта сборщик
+//! не принимают и не измеряются:
+//!  * [`crate::counting_sort`], [`crate::radix_sort_lsd`], [`crate::radix_sort_msd`],
+//!    [`crate::bucket_sort`] — не сравнивают элементы и работают только с числовыми
+//!    ключами, поэтому не подходят под общий вызов [`Algorithm`];
+//!  * [`crate::nth_element`], [`crate::partial_sort`], [`crate::top_k`] — упорядочивают
+//!    только часть элементов;
+//!  * [`crate::sort_small`] — сортирующая сеть с постоянным числом сравнений;
+//!  * параллельные сортировки ([`crate::ParallelConfig`]) — сборщик пришлось бы делить
+//!    между потоками.
+
+use std::{cell::RefCell, cmp::Ordering, mem::MaybeUninit, ops::Range, ptr};
+
+use crate::{
+    MergeMode, MergeSorter, bubble_sort_with,
+    dual_pivot::dual_pivot_quick_sort_with,
+    heap_sort_with, insertion_sort_with,
+    intro_sort::intro_sort_with,
+    merge_sort_with,
+    quadratic::{
+        binary_insertion_sort_with, cocktail_shaker_sort_with, comb_sort_with, gnome_sort_with,
+    },
+    quick_sort_with, selection_sort_with,
+    shell_sort::{ShellGaps, shell_sort_with},
+    three_way::three_way_quick_sort_with,
+    trace::{Location, TraceEvent},
+};
+
+/// Сортировки, которые можно измерить ([`SortStats`]) и записать ([`crate::Trace`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// [`crate::bubble_sort`].
+    Bubble,
+    /// [`crate::selection_sort`].
+    Selection,
+    /// [`crate::insertion_sort`].
+    Insertion,
+    /// [`crate::binary_insertion_sort`].
+    BinaryInsertion,
+    /// [`crate::gnome_sort`].
+    Gnome,
+    /// [`crate::cocktail_shaker_sort`].
+    CocktailShaker,
+    /// [`crate::comb_sort`].
+    Comb,
+    /// [`crate::shell_sort()`].
+    Shell,
+    /// [`crate::quick_sort`].
+    Quick,
+    /// [`crate::three_way_quick_sort`].
+    ThreeWayQuick,
+    /// [`crate::dual_pivot_quick_sort`].
+    DualPivotQuick,
+    /// [`crate::intro_sort()`].
+    Intro,
+    /// [`crate::heap_sort`].
+    Heap,
+    /// [`crate::merge_sort`].
+    Merge,
+    /// [`MergeSorter`] в режиме [`MergeMode::BottomUp`].
+    BottomUpMerge,
+    /// [`MergeSorter`] в режиме [`MergeMode::Natural`].
+    NaturalMerge,
+}
+
+impl Algorithm {
+    /// Все сортировки.
+    pub const ALL: [Self; 16] = [
+        Self::Bubble,
+        Self::Selection,
+        Self::Insertion,
+        Self::BinaryInsertion,
+        Self::Gnome,
+        Self::CocktailShaker,
+        Self::Comb,
+        Self::Shell,
+        Self::Quick,
+        Self::ThreeWayQuick,
+        Self::DualPivotQuick,
+        Self::Intro,
+        Self::Heap,
+        Self::Merge,
+        Self::BottomUpMerge,
+        Self::NaturalMerge,
+    ];
+
+    /// Сортирует `arr`, сообщая о событиях `collector`.
+    pub(crate) fn sort_with<T, F, C>(self, arr: &mut [T], compare: F, collector: &mut C)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        C: Collector<T>,
+    {
+        match self {
+            Self::Bubble => bubble_sort_with(arr, compare, collector),
+            Self::Selection => selection_sort_with(arr, compare, collector),
+            Self::Insertion => insertion_sort_with(arr, compare, collector),
+            Self::BinaryInsertion => binary_insertion_sort_with(arr, compare, collector),
+            Self::Gnome => gnome_sort_with(arr, compare, collector),
+            Self::CocktailShaker => cocktail_shaker_sort_with(arr, compare, collector),
+            Self::Comb => comb_sort_with(arr, compare, collector),
+            Self::Shell => shell_sort_with(arr, ShellGaps::default(), compare, collector),
+            Self::Quick => quick_sort_with(arr, compare, collector),
+            Self::ThreeWayQuick => three_way_quick_sort_with(arr, compare, collector),
+            Self::DualPivotQuick => dual_pivot_quick_sort_with(arr, compare, collector),
+            Self::Intro => intro_sort_with(arr, compare, collector),
+            Self::Heap => heap_sort_with(arr, compare, collector),
+            Self::Merge => merge_sort_with(arr, compare, collector),
+            Self::BottomUpMerge => {
+                MergeSorter::new(MergeMode::BottomUp).sort_with(arr, compare, collector);
+            }
+            Self::NaturalMerge => {
+                MergeSorter::new(MergeMode::Natural).sort_with(arr, compare, collector);
+            }
+        }
+    }
+}
+
+/// Получатель событий сортировки. Методы по умолчанию ничего не делают.
+///
+/// Индексы [`Location::Slice`] в событиях отсчитываются от начала всего сортируемого
+/// среза: алгоритм, работающий с его частью, прибавляет [`Collector::offset`].
+pub(crate) trait Collector<T> {
+    /// Положение начала `arr` во всём сортируемом срезе.
+    fn offset(&mut self, _arr: &[T]) -> usize {
+        0
+    }
+
+    /// Выполнен обмен `arr[a]` и `arr[b]`.
+    fn swap(&mut self, _arr: &[T], _a: usize, _b: usize) {}
+
+    /// Элемент перемещён из `src` в `dst`.
+    fn write(&mut self, _dst: Location, _src: Location) {}
+
+    /// Выделен буфер `buf`; ячейка `buf[i]` — [`Location::Buffer`]`(i)`.
+    fn alloc(&mut self, _buf: &[MaybeUninit<T>]) {}
+}
+
+/// Сборщик без измерения.
+impl<T> Collector<T> for () {}
+
+/// Обмен `arr[a]` и `arr[b]`.
+#[inline]
+pub(crate) fn swap<T, C: Collector<T>>(arr: &mut [T], a: usize, b: usize, collector: &mut C) {
+    arr.swap(a, b);
+    collector.swap(arr, a, b);
+}
+
+/// Разворот среза, учитывается как последовательность обменов.
+#[inline]
+pub(crate) fn reverse<T, C: Collector<T>>(arr: &mut [T], collector: &mut C) {
+    arr.reverse();
+    let len = arr.len();
+    for i in 0..len / 2 {
+        collector.swap(arr, i, len - 1 - i);
+    }
+}
+
+/// Циклический сдвиг среза вправо на один элемент: последний элемент встаёт в начало.
+/// Учитывается как перенос последнего элемента во временную переменную, сдвиг
+/// остальных и запись на первое место.
+#[inline]
+pub(crate) fn rotate_right<T, C: Collector<T>>(arr: &mut [T], collector: &mut C) {
+    arr.rotate_right(1);
+    let offset = collector.offset(arr);
+    let last = offset + arr.len() - 1;
+    collector.write(Location::Temp, Location::Slice(last));
+    for i in (offset..last).rev() {
+        collector.write(Location::Slice(i + 1), Location::Slice(i));
+    }
+    collector.write(Location::Slice(offset), Location::Temp);
+}
+
+/// Статистика работы сортировки.
+///
+/// Измеряются только сортировки из [`Algorithm`]; какие функции не измеряются и почему,
+/// описано в [документации модуля](self).
+///
+/// # Примеры
+///
+/// Сортировка выбором делает не больше `n - 1` обменов, а пузырёк — по обмену
+/// на каждую инверсию:
+///
+/// ```
+/// use exa_sort::{Algorithm, SortStats};
+///
+/// let source = [10, 4, 7, 3, 1, 8, 2, 9, 5, 6, 15, 12, 18, 11, 14, 17, 13, 16, 19, 1];
+///
+/// let mut arr = source;
+/// let bubble = SortStats::measure(&mut arr, Algorithm::Bubble);
+/// let mut arr = source;
+/// let selection = SortStats::measure(&mut arr, Algorithm::Selection);
+///
+/// assert_eq!(bubble.comparisons, selection.comparisons);
+/// assert!(selection.swaps < source.len());
+/// assert!(selection.swaps < bubble.swaps);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SortStats {
+    /// Количество вызовов функции сравнения.
+    pub comparisons: usize,
+    /// Количество обменов двух элементов.
+    pub swaps: usize,
+    /// Количество перемещений отдельных элементов (не считая обменов):
+    /// в буфер, из буфера, сдвиги.
+    pub writes: usize,
+    /// Количество выделений дополнительной памяти под элементы.
+    pub allocations: usize,
+    /// Суммарный размер выделенной памяти в байтах.
+    pub allocated_bytes: usize,
+}
+
+impl SortStats {
+    /// Пустая статистика.
+    pub const fn new() -> Self {
+        Self {
+            comparisons: 0,
+            swaps: 0,
+            writes: 0,
+            allocations: 0,
+            allocated_bytes: 0,
+        }
+    }
+
+    /// Сортирует `arr` алгоритмом `algorithm` по возрастанию и возвращает статистику.
+    pub fn measure<T: Ord>(arr: &mut [T], algorithm: Algorithm) -> Self {
+        Self::measure_by(arr, T::cmp, algorithm)
+    }
+
+    /// Аналог [`SortStats::measure`] с пользовательской функцией сравнения.
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// use exa_sort::{Algorithm, SortStats};
+    ///
+    /// let mut arr: Vec<u32> = (0..1000).rev().collect();
+    /// let heap = SortStats::measure_by(&mut arr, |a, b| b.cmp(a), Algorithm::Heap);
+    /// assert_eq!(heap.allocations, 0);
+    ///
+    /// let merge = SortStats::measure_by(&mut arr, |a, b| a.cmp(b), Algorithm::Merge);
+    /// assert_eq!(merge.allocations, 1);
+    /// assert!(merge.writes > 0);
+    /// ```
+    pub fn measure_by<T, F>(arr: &mut [T], mut compare: F, algorithm: Algorithm) -> Self
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut stats = Self::new();
+        let mut comparisons = 0;
+        let counted = |a: &T, b: &T| {
+            comparisons += 1;
+            compare(a, b)
+        };
+        algorithm.sort_with(arr, counted, &mut stats);
+        stats.comparisons = comparisons;
+        stats
+    }
+}
+
+impl<T> Collector<T> for SortStats {
+    fn swap(&mut self, _arr: &[T], _a: usize, _b: usize) {
+        self.swaps += 1;
+    }
+
+    fn write(&mut self, _dst: Location, _src: Location) {
+        self.writes += 1;
+    }
+
+    fn alloc(&mut self, buf: &[MaybeUninit<T>]) {
+        if !buf.is_empty() {
+            self.allocations += 1;
+            self.allocated_bytes += size_of_val(buf);
+        }
+    }
+}
+
+/// Запись событий сортировки для [`crate::Trace`].
+pub(crate) struct Tracer {
+    /// Адреса сортируемого среза и текущего буфера, размер элемента.
+    slice: Range<usize>,
+    buffer: Range<usize>,
+    size: usize,
+    pub(crate) stats: SortStats,
+    pub(crate) events: Vec<TraceEvent>,
+}
+
+impl Tracer {
+    pub(crate) fn new<T>(arr: &[T]) -> Self {
+        Self {
+            slice: addresses(arr),
+            buffer: 0..0,
+            size: size_of::<T>(),
+            stats: SortStats::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Записывает сравнение элементов `a` и `b`.
+    pub(crate) fn compare<T>(&mut self, a: &T, b: &T) {
+        self.stats.comparisons += 1;
+        let event = TraceEvent::Compare(self.location(a), self.location(b));
+        self.events.push(event);
+    }
+
+    /// Положение элемента по его адресу.
+    fn location<T>(&self, element: &T) -> Location {
+        let addr = ptr::from_ref(element).addr();
+        if self.size == 0 {
+            Location::Slice(0)
+        } else if self.slice.contains(&addr) {
+            Location::Slice((addr - self.slice.start) / self.size)
+        } else if self.buffer.contains(&addr) {
+            Location::Buffer((addr - self.buffer.start) / self.size)
+        } else {
+            Location::Temp
+        }
+    }
+}
+
+/// Диапазон адресов элементов среза.
+fn addresses<T>(arr: &[T]) -> Range<usize> {
+    let start = arr.as_ptr().addr();
+    start..start + size_of_val(arr)
+}
+
+/// Трассировщик — сборщик. Сравнения записывает обёртка над функцией сравнения,
+/// поэтому сборщиком служит общая ссылка.
+impl<T> Collector<T> for &RefCell<Tracer> {
+    fn offset(&mut self, arr: &[T]) -> usize {
+        let tracer = self.borrow();
+        (arr.as_ptr().addr() - tracer.slice.start)
+            .checked_div(tracer.size)
+            .unwrap_or(0)
+    }
+
+    fn swap(&mut self, arr: &[T], a: usize, b: usize) {
+        let offset = self.offset(arr);
+        let mut tracer = self.borrow_mut();
+        tracer.stats.swaps += 1;
+        let event = TraceEvent::Swap(Location::Slice(offset + a), Location::Slice(offset + b));
+        tracer.events.push(event);
+    }
+
+    fn write(&mut self, dst: Location, src: Location) {
+        let mut tracer = self.borrow_mut();
+        tracer.stats.writes += 1;
+        tracer.events.push(TraceEvent::Write { dst, src });
+    }
+
+    fn alloc(&mut self, buf: &[MaybeUninit<T>]) {
+        let mut tracer = self.borrow_mut();
+        Collector::<T>::alloc(&mut tracer.stats, buf);
+        tracer.buffer = addresses(buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn values(len: usize) -> Vec<u32> {
+        let mut rng = StdRng::seed_from_u64(0x2545_f491);
+        (0..len).map(|_| rng.random_range(0..1 << 24)).collect()
+    }
+
+    fn measure(values: &[u32], algorithm: Algorithm) -> SortStats {
+        let mut arr = values.to_vec();
+        let stats = SortStats::measure(&mut arr, algorithm);
+        assert!(arr.is_sorted(), "{algorithm:?}");
+        stats
+    }
+
+    #[test]
+    fn test_quadratic() {
+        let n = 500;
+        let values = values(n);
+        let all = n * (n - 1) / 2;
+
+        let bubble = measure(&values, Algorithm::Bubble);
+        let selection = measure(&values, Algorithm::Selection);
+        let insertion = measure(&values, Algorithm::BinaryInsertion);
+
+        // Пузырёк и выбор всегда сравнивают все пары.
+        assert_eq!(bubble.comparisons, all);
+        assert_eq!(selection.comparisons, all);
+        // Выбор: не больше n - 1 обменов. Пузырёк: обмен на каждую инверсию, в среднем n²/4.
+        assert!(selection.swaps < n);
+        assert!(bubble.swaps > all / 3);
+        // Двоичные вставки: O(n log n) сравнений.
+        assert!(insertion.comparisons < n * 10);
+        assert_eq!(insertion.swaps, 0);
+    }
+
+    #[test]
+    fn test_sorted_input() {
+        let sorted: Vec<u32> = (0..1000).collect();
+
+        let insertion = measure(&sorted, Algorithm::BinaryInsertion);
+        let gnome = measure(&sorted, Algorithm::Gnome);
+        let cocktail = measure(&sorted, Algorithm::CocktailShaker);
+        let natural = measure(&sorted, Algorithm::NaturalMerge);
+
+        assert_eq!(gnome.comparisons, 999);
+        assert_eq!(cocktail.comparisons, 999);
+        assert_eq!(natural.comparisons, 999);
+        for stats in [insertion, gnome, cocktail, natural] {
+            assert_eq!(stats.swaps, 0);
+            assert_eq!(stats.allocations, 0);
+        }
+    }
+
+    #[test]
+    fn test_n_log_n() {
+        let n = 1 << 12;
+        let log_n = 12;
+        let values = values(n);
+
+        let merge = measure(&values, Algorithm::Merge);
+        let heap = measure(&values, Algorithm::Heap);
+        let intro = measure(&values, Algorithm::Intro);
+        let shell = measure(&values, Algorithm::Shell);
+
+        // Сортировка слиянием: не больше n log n сравнений и один буфер на n/2 элементов.
+        assert!(merge.comparisons <= n * log_n);
+        assert_eq!(merge.allocations, 1);
+        assert_eq!(merge.allocated_bytes, n / 2 * size_of::<u32>());
+        assert_eq!(merge.swaps, 0);
+        // Пирамидальная: не больше 2 n log n сравнений, без дополнительной памяти.
+        assert!(heap.comparisons <= 2 * n * log_n);
+        assert_eq!(heap.allocations, 0);
+        assert!(intro.comparisons <= 2 * n * log_n);
+        assert_eq!(intro.allocations, 0);
+        assert!(shell.comparisons <= 2 * n * log_n);
+    }
+
+    #[test]
+    fn test_matches_plain_sort() {
+        // Сборщик не меняет ход сортировки: сравнения те же, что у `*_by` варианта.
+        let values = values(1000);
+        for algorithm in Algorithm::ALL {
+            let stats = measure(&values, algorithm);
+            let mut arr = values.clone();
+            let mut comparisons = 0;
+            algorithm.sort_with(
+                &mut arr,
+                |a: &u32, b: &u32| {
+                    comparisons += 1;
+                    a.cmp(b)
+                },
+                &mut (),
+            );
+            assert_eq!(stats.comparisons, comparisons, "{algorithm:?}");
+        }
+    }
+}
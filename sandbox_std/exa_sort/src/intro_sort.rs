@@ -0,0 +1,267 @@
+//! Интроспективная сортировка (Introsort).
+//!
+//! Гибрид быстрой сортировки, пирамидальной сортировки и сортирующих сетей:
+//!  * опорный элемент выбирается медианой трёх (или «девяткой» Тьюки на больших срезах),
+//!    поэтому уже отсортированные и развёрнутые массивы разбиваются пополам;
+//!  * разбиение Хоара останавливается на равных опорному элементах и делит поровну
+//!    массивы с большим количеством повторов;
+//!  * при превышении глубины рекурсии `2 * log2(n)` срез досортировывается
+//!    [`crate::heap_sort_by`], что гарантирует O(n log n) в худшем случае;
+//!  * срезы не длиннее [`NETWORK_THRESHOLD`] сортируются сортирующей сетью
+//!    (см. [`crate::small`]).
+//!
+//! Рекурсия выполняется только для меньшей части разбиения, поэтому глубина стека не
+//! превышает O(log n).
+
+use std::{cmp::Ordering, mem};
+
+use crate::{
+    heap_sort_with,
+    instrument::{Collector, swap},
+    small::sort_network,
+};
+
+/// Наибольшая длина среза, который сортируется сортирующей сетью.
+pub const NETWORK_THRESHOLD: usize = 16;
+
+/// Длина среза, начиная с которой опорный элемент выбирается «девяткой» Тьюки.
+const NINTHER_THRESHOLD: usize = 128;
+
+/// Интроспективная сортировка (Introsort)
+/// Быстрая сортировка с защитой от худшего случая: сложность O(n log n) гарантирована.
+/// Сортировка нестабильна.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::intro_sort;
+///
+/// let mut arr = [2, 3, 1];
+/// intro_sort(&mut arr);
+/// assert_eq!(arr, [1, 2 ,3]);
+/// ```
+///
+/// ```
+/// use exa_sort::intro_sort;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// intro_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_sort::intro_sort;
+///
+/// let mut arr = [10, 4, 7, 3, 1, 8, 2, 9, 5, 6, 15, 12, 18, 11, 14, 17, 13, 16, 19, 1];
+/// intro_sort(&mut arr);
+/// assert_eq!(arr, [1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
+/// ```
+pub fn intro_sort<T: Ord>(arr: &mut [T]) {
+    intro_sort_by(arr, T::cmp);
+}
+
+/// Интроспективная сортировка с пользовательской функцией сравнения.
+/// Аналог [`intro_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::intro_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// intro_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn intro_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    intro_sort_with(arr, compare, &mut ());
+}
+
+/// Интроспективная сортировка по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::intro_sort_by_key;
+///
+/// let mut arr = ["ccc", "a", "bb"];
+/// intro_sort_by_key(&mut arr, |a| a.len());
+/// assert_eq!(arr, ["a", "bb", "ccc"]);
+/// ```
+pub fn intro_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    intro_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn intro_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    let depth_limit = 2 * (usize::BITS - arr.len().leading_zeros());
+    intro_sort_helper(arr, depth_limit, &mut compare, collector);
+}
+
+fn intro_sort_helper<T, F, C>(
+    mut arr: &mut [T],
+    mut depth_limit: u32,
+    compare: &mut F,
+    collector: &mut C,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    loop {
+        let len = arr.len();
+        if len <= NETWORK_THRESHOLD {
+            sort_network(arr, compare, collector);
+            return;
+        }
+        if depth_limit == 0 {
+            heap_sort_with(arr, &mut *compare, collector);
+            return;
+        }
+        depth_limit -= 1;
+
+        let pivot = choose_pivot(arr, compare);
+        swap(arr, 0, pivot, collector);
+        let mid = hoare_partition(arr, compare, collector);
+
+        // Рекурсивно сортируем меньшую часть, большую обрабатываем в цикле.
+        let (left, right) = mem::take(&mut arr).split_at_mut(mid);
+        let right = &mut right[1..];
+        if left.len() < right.len() {
+            intro_sort_helper(left, depth_limit, compare, collector);
+            arr = right;
+        } else {
+            intro_sort_helper(right, depth_limit, compare, collector);
+            arr = left;
+        }
+    }
+}
+
+/// Индекс опорного элемента: медиана трёх, а для больших срезов — медиана трёх медиан.
+pub(crate) fn choose_pivot<T, F>(arr: &[T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let len = arr.len();
+    let (a, b, c) = (len / 4, len / 2, len / 4 * 3);
+
+    if len < NINTHER_THRESHOLD {
+        return median_of_three(arr, a, b, c, compare);
+    }
+
+    let step = len / 8;
+    let a = median_of_three(arr, a - step, a, a + step, compare);
+    let b = median_of_three(arr, b - step, b, b + step, compare);
+    let c = median_of_three(arr, c - step, c, c + step, compare);
+    median_of_three(arr, a, b, c, compare)
+}
+
+/// Индекс медианы из элементов `arr[a]`, `arr[b]` и `arr[c]`.
+fn median_of_three<T, F>(arr: &[T], a: usize, b: usize, c: usize, compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let less = |compare: &mut F, x: usize, y: usize| compare(&arr[x], &arr[y]) == Ordering::Less;
+
+    if less(compare, a, b) {
+        if less(compare, b, c) {
+            b
+        } else if less(compare, a, c) {
+            c
+        } else {
+            a
+        }
+    } else if less(compare, a, c) {
+        a
+    } else if less(compare, b, c) {
+        c
+    } else {
+        b
+    }
+}
+
+/// Разбиение Хоара относительно опорного элемента `arr[0]`.
+/// Возвращает итоговую позицию опорного элемента: слева от неё элементы не больше,
+/// справа — не меньше опорного. Равные опорному элементы распределяются по обе стороны.
+pub(crate) fn hoare_partition<T, F, C>(arr: &mut [T], compare: &mut F, collector: &mut C) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    let (pivot, rest) = arr.split_first_mut().expect("срез не пуст");
+    let mut left = 0;
+    let mut right = rest.len();
+
+    loop {
+        while left < right && compare(&rest[left], pivot) == Ordering::Less {
+            left += 1;
+        }
+        while left < right && compare(&rest[right - 1], pivot) == Ordering::Greater {
+            right -= 1;
+        }
+        if left >= right {
+            break;
+        }
+        right -= 1;
+        swap(rest, left, right, collector);
+        left += 1;
+    }
+
+    swap(arr, 0, left, collector);
+    left
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEN: usize = 1_000_000;
+
+    #[test]
+    fn test_sorted() {
+        let mut arr: Vec<u32> = (0..LEN as u32).collect();
+        let expected = arr.clone();
+        intro_sort(&mut arr);
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_reversed() {
+        let mut arr: Vec<u32> = (0..LEN as u32).rev().collect();
+        intro_sort(&mut arr);
+        assert!(arr.is_sorted());
+        assert_eq!(arr.len(), LEN);
+    }
+
+    #[test]
+    fn test_all_equal() {
+        let mut arr = vec![7u8; LEN];
+        intro_sort(&mut arr);
+        assert!(arr.iter().all(|&v| v == 7));
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let mut arr: Vec<u32> = (0..LEN as u32).map(|i| i % 100).collect();
+        let mut expected = arr.clone();
+        expected.sort();
+        intro_sort(&mut arr);
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        // Нулевая глубина сразу переключает алгоритм на пирамидальную сортировку.
+        let mut arr: Vec<u32> = (0..1000).rev().collect();
+        intro_sort_helper(&mut arr, 0, &mut u32::cmp, &mut ());
+        assert!(arr.is_sorted());
+    }
+}
@@ -1,8 +1,81 @@
 //! Тестирование различных типов сортировок.
 //! Сравнить со стандартной сортировкой.
 //!
+//! | Алгоритм                      | Среднее     | Худшее     | Память   | Стабильна |
+//! |-------------------------------|-------------|------------|----------|-----------|
+//! | [`bubble_sort`]               | O(n²)       | O(n²)      | O(1)     | да        |
+//! | [`cocktail_shaker_sort`]      | O(n²)       | O(n²)      | O(1)     | да        |
+//! | [`comb_sort`]                 | O(n²/2^p)   | O(n²)      | O(1)     | нет       |
+//! | [`gnome_sort`]                | O(n²)       | O(n²)      | O(1)     | да        |
+//! | [`selection_sort`]            | O(n²)       | O(n²)      | O(1)     | нет       |
+//! | [`insertion_sort`]            | O(n²)       | O(n²)      | O(1)     | да        |
+//! | [`binary_insertion_sort`]     | O(n²)       | O(n²)      | O(1)     | да        |
+//! | [`shell_sort`](fn@shell_sort) | ~O(n^(4/3)) | O(n^(3/2)) | O(1)     | нет       |
+//! | [`quick_sort`]                | O(n log n)  | O(n²)      | O(n)     | нет       |
+//! | [`three_way_quick_sort`]      | O(n log k)  | O(n²)      | O(log n) | нет       |
+//! | [`dual_pivot_quick_sort`]     | O(n log n)  | O(n²)      | O(log n) | нет       |
+//! | [`intro_sort`](fn@intro_sort) | O(n log n)  | O(n log n) | O(log n) | нет       |
+//! | [`merge_sort`]                | O(n log n)  | O(n log n) | O(n)     | да        |
+//! | [`heap_sort`]                 | O(n log n)  | O(n log n) | O(1)     | нет       |
+//! | [`counting_sort`]             | O(n + k)    | O(n + k)   | O(n + k) | да        |
+//! | [`radix_sort_lsd`]            | O(w · n)    | O(w · n)   | O(n)     | да        |
+//! | [`radix_sort_msd`]            | O(w · n)    | O(w · n)   | O(w)     | нет       |
+//! | [`bucket_sort`]               | O(n)        | O(n²)      | O(n)     | —         |
+//!
+//! `k` — диапазон значений, `w` — длина ключа в байтах, `p` — количество уменьшений шага.
+//! Замеры времени — в `benches/`.
+//!
+
+use std::{cmp::Ordering, ptr};
 
-use std::fmt::Debug;
+use instrument::{Collector, swap};
+use small::{MAX_NETWORK, insertion_kernel};
+
+pub mod bucket;
+pub mod counting;
+pub mod dual_pivot;
+pub mod external;
+pub mod instrument;
+pub mod intro_sort;
+pub mod merge_sorter;
+pub mod parallel;
+pub mod quadratic;
+pub mod radix;
+pub mod select;
+pub mod shell_sort;
+pub mod small;
+pub mod three_way;
+pub mod trace;
+
+pub use bucket::bucket_sort;
+pub use counting::{counting_sort, counting_sort_by_key};
+pub use dual_pivot::{
+    dual_pivot_quick_sort, dual_pivot_quick_sort_by, dual_pivot_quick_sort_by_key,
+};
+pub use external::{ExternalSorter, RecordFormat};
+pub use instrument::{Algorithm, SortStats};
+pub use intro_sort::{intro_sort, intro_sort_by, intro_sort_by_key};
+pub use merge_sorter::{MergeMode, MergeSorter};
+pub use parallel::{
+    ParallelConfig, parallel_merge_sort, parallel_merge_sort_by, parallel_merge_sort_by_key,
+    parallel_quick_sort, parallel_quick_sort_by, parallel_quick_sort_by_key,
+};
+pub use quadratic::{
+    binary_insertion_sort, binary_insertion_sort_by, binary_insertion_sort_by_key,
+    cocktail_shaker_sort, cocktail_shaker_sort_by, cocktail_shaker_sort_by_key, comb_sort,
+    comb_sort_by, comb_sort_by_key, gnome_sort, gnome_sort_by, gnome_sort_by_key,
+};
+pub use radix::{RadixKey, radix_sort_bytes, radix_sort_lsd, radix_sort_msd};
+pub use select::{
+    nth_element, nth_element_by, nth_element_by_key, partial_sort, partial_sort_by,
+    partial_sort_by_key, top_k, top_k_by, top_k_by_key,
+};
+pub use shell_sort::{
+    ShellGaps, shell_sort, shell_sort_by, shell_sort_by_key, shell_sort_with_gaps,
+};
+pub use small::{sort_small, sort_small_by, sort_small_by_key};
+pub use three_way::{three_way_quick_sort, three_way_quick_sort_by, three_way_quick_sort_by_key};
+pub use trace::{Location, Trace, TraceEvent};
 
 /// Сортировка пузырьком (Bubble Sort)
 /// Простой, но неэффективный алгоритм сортировки с временной сложностью O(n²).
@@ -37,11 +110,57 @@ use std::fmt::Debug;
 /// small - time:   [3.9278 ns 3.9323 ns 3.9369 ns]
 /// big - time:   [370.78 µs 375.28 µs 379.72 µs]
 pub fn bubble_sort<T: Ord>(arr: &mut [T]) {
+    bubble_sort_by(arr, T::cmp);
+}
+
+/// Сортировка пузырьком с пользовательской функцией сравнения.
+/// Аналог [`bubble_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::bubble_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// bubble_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn bubble_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    bubble_sort_with(arr, compare, &mut ());
+}
+
+/// Сортировка пузырьком по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::bubble_sort_by_key;
+///
+/// let mut arr = [-5i32, 2, -8, 1, 9];
+/// bubble_sort_by_key(&mut arr, |a| a.abs());
+/// assert_eq!(arr, [1, 2, -5, -8, 9]);
+/// ```
+pub fn bubble_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    bubble_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn bubble_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
     let len = arr.len();
     for i in 0..len {
         for a in 0..len - 1 - i {
-            if arr[a] > arr[a + 1] {
-                arr.swap(a, a + 1);
+            if compare(&arr[a], &arr[a + 1]) == Ordering::Greater {
+                swap(arr, a, a + 1, collector);
             }
         }
     }
@@ -52,6 +171,7 @@ pub fn bubble_sort<T: Ord>(arr: &mut [T]) {
 /// и обмена его с первым элементом неотсортированной части. Так происходит поочередно для каждого
 /// элемента. Имеет временную сложность O(n²), но обычно работает быстрее сортировки пузырьком,
 /// так как делает меньше обменов(на больших массивах).
+/// Количество сравнений и обменов можно измерить с помощью [`SortStats`].
 ///
 ///
 /// # Примеры
@@ -84,16 +204,62 @@ pub fn bubble_sort<T: Ord>(arr: &mut [T]) {
 /// big - time:   [232.99 µs 259.58 µs 286.13 µs]
 ///
 pub fn selection_sort<T: Ord>(arr: &mut [T]) {
+    selection_sort_by(arr, T::cmp);
+}
+
+/// Сортировка выбором с пользовательской функцией сравнения.
+/// Аналог [`selection_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::selection_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// selection_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn selection_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    selection_sort_with(arr, compare, &mut ());
+}
+
+/// Сортировка выбором по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::selection_sort_by_key;
+///
+/// let mut arr = ["ccc", "a", "bb"];
+/// selection_sort_by_key(&mut arr, |a| a.len());
+/// assert_eq!(arr, ["a", "bb", "ccc"]);
+/// ```
+pub fn selection_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    selection_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn selection_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
     let len = arr.len();
     for current in 0..len {
         let mut min_index = current;
         for i in current + 1..len {
-            if arr[i] < arr[min_index] {
+            if compare(&arr[i], &arr[min_index]) == Ordering::Less {
                 min_index = i;
             }
         }
         if min_index != current {
-            arr.swap(current, min_index);
+            swap(arr, current, min_index, collector);
         }
     }
 }
@@ -128,20 +294,61 @@ pub fn selection_sort<T: Ord>(arr: &mut [T]) {
 /// insertion_sort(&mut arr);
 /// assert_eq!(arr, [1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
 /// ```
+pub fn insertion_sort<T: Ord>(arr: &mut [T]) {
+    insertion_sort_by(arr, T::cmp);
+}
+
+/// Сортировка вставками с пользовательской функцией сравнения.
+/// Аналог [`insertion_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::insertion_sort_by;
 ///
-/// small - time:   [3.2941 ns 3.2994 ns 3.3058 ns]
-/// big - time:   [98.217 µs 98.390 µs 98.560 µs]
+/// let mut arr = [5, 2, 8, 1, 9];
+/// insertion_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn insertion_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    insertion_sort_with(arr, compare, &mut ());
+}
+
+/// Сортировка вставками по ключу, который вычисляет `key`.
 ///
-pub fn insertion_sort<T: Ord>(arr: &mut [T]) {
+/// # Примеры
+///
+/// ```
+/// use exa_sort::insertion_sort_by_key;
+///
+/// let mut arr = [(3, 'c'), (1, 'a'), (2, 'b')];
+/// insertion_sort_by_key(&mut arr, |&(n, _)| n);
+/// assert_eq!(arr, [(1, 'a'), (2, 'b'), (3, 'c')]);
+/// ```
+pub fn insertion_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    insertion_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn insertion_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
     let len = arr.len();
 
     for current in 1..len {
-        for index in 0..current {
-            if arr[current] >= arr[index] {
-                continue;
-            }
-
-            arr.swap(current, index);
+        // Элемент сдвигается влево только мимо строго больших, поэтому сортировка стабильна.
+        let mut index = current;
+        while index > 0 && compare(&arr[index], &arr[index - 1]) == Ordering::Less {
+            swap(arr, index, index - 1, collector);
+            index -= 1;
         }
     }
 }
@@ -181,33 +388,99 @@ pub fn insertion_sort<T: Ord>(arr: &mut [T]) {
 /// small - [12.039 ns 12.061 ns 12.085 ns]
 /// big - [30.098 µs 30.134 µs 30.167 µs]
 ///
-pub fn quick_sort<T: Ord + Copy + Debug>(arr: &mut [T]) {
+pub fn quick_sort<T: Ord>(arr: &mut [T]) {
+    quick_sort_by(arr, T::cmp);
+}
+
+/// Быстрая сортировка с пользовательской функцией сравнения.
+/// Аналог [`quick_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::quick_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// quick_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn quick_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    quick_sort_with(arr, compare, &mut ());
+}
+
+/// Быстрая сортировка по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::quick_sort_by_key;
+///
+/// let mut arr = [-5i32, 2, -8, 1, 9];
+/// quick_sort_by_key(&mut arr, |a| a.abs());
+/// assert_eq!(arr, [1, 2, -5, -8, 9]);
+/// ```
+pub fn quick_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    quick_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn quick_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
     if arr.len() > 1 {
-        quick_sort_helper(arr, 0, arr.len() - 1);
+        quick_sort_helper(arr, 0, arr.len() - 1, &mut compare, collector);
     }
 }
 
-fn quick_sort_helper<T: Ord + Copy + Debug>(arr: &mut [T], low: usize, high: usize) {
+fn quick_sort_helper<T, F, C>(
+    arr: &mut [T],
+    low: usize,
+    high: usize,
+    compare: &mut F,
+    collector: &mut C,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
     if low < high {
-        let pi = quick_sort_partition(arr, low, high);
+        let pi = quick_sort_partition(arr, low, high, compare, collector);
         if pi > 0 {
-            quick_sort_helper(arr, low, pi.wrapping_sub(1));
+            quick_sort_helper(arr, low, pi.wrapping_sub(1), compare, collector);
         }
-        quick_sort_helper(arr, pi + 1, high);
+        quick_sort_helper(arr, pi + 1, high, compare, collector);
     }
 }
 
-fn quick_sort_partition<T: Ord + Copy + Debug>(arr: &mut [T], low: usize, high: usize) -> usize {
-    let pivot = arr[high];
+fn quick_sort_partition<T, F, C>(
+    arr: &mut [T],
+    low: usize,
+    high: usize,
+    compare: &mut F,
+    collector: &mut C,
+) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    // Опорный элемент остаётся на месте `high` до конца разбиения,
+    // поэтому сравниваем с ним по индексу, без копирования.
     let mut i = low;
 
     for j in low..high {
-        if arr[j] <= pivot {
-            arr.swap(i, j);
+        if compare(&arr[j], &arr[high]) != Ordering::Greater {
+            swap(arr, i, j, collector);
             i += 1;
         }
     }
-    arr.swap(i, high);
+    swap(arr, i, high, collector);
     i
 }
 
@@ -242,47 +515,178 @@ fn quick_sort_partition<T: Ord + Copy + Debug>(arr: &mut [T], low: usize, high:
 /// merge_sort(&mut arr);
 /// assert_eq!(arr, [1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
 /// ```
+pub fn merge_sort<T: Ord>(arr: &mut [T]) {
+    merge_sort_by(arr, T::cmp);
+}
+
+/// Сортировка слиянием с пользовательской функцией сравнения.
+/// Аналог [`merge_sort`], порядок элементов задаёт `compare`. Сортировка стабильна.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::merge_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// merge_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn merge_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    merge_sort_with(arr, compare, &mut ());
+}
+
+/// Сортировка слиянием по ключу, который вычисляет `key`.
+/// Элементы с равными ключами сохраняют исходный порядок.
 ///
-/// small - time:   [58.488 ns 58.561 ns 58.640 ns]
-/// big - time:   [20.218 µs 20.283 µs 20.345 µs]
+/// # Примеры
+///
+/// ```
+/// use exa_sort::merge_sort_by_key;
 ///
-pub fn merge_sort<T: Ord + Copy + Debug>(arr: &mut [T]) {
+/// let mut arr = [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+/// merge_sort_by_key(&mut arr, |&(n, _)| n);
+/// assert_eq!(arr, [(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+/// ```
+pub fn merge_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    merge_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn merge_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    if arr.len() <= MAX_NETWORK {
+        insertion_kernel(arr, &mut compare, collector);
+        return;
+    }
+    let mut buf = Vec::with_capacity(arr.len() / 2);
+    collector.alloc(buf.spare_capacity_mut());
+    merge_sort_helper(arr, &mut buf, &mut compare, collector);
+}
+
+pub(crate) fn merge_sort_helper<T, F, C>(
+    arr: &mut [T],
+    buf: &mut Vec<T>,
+    compare: &mut F,
+    collector: &mut C,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
     let len = arr.len();
-    if len <= 1 {
+    if len <= MAX_NETWORK {
+        insertion_kernel(arr, compare, collector);
         return;
     }
     let mid = len / 2;
 
-    merge_sort(&mut arr[..mid]);
-    merge_sort(&mut arr[mid..]);
+    merge_sort_helper(&mut arr[..mid], buf, compare, collector);
+    merge_sort_helper(&mut arr[mid..], buf, compare, collector);
 
-    let mut merge = Vec::with_capacity(len);
-    let (left, right) = arr.split_at(mid);
+    merge(arr, mid, buf, compare, collector);
+}
 
-    let left_len = left.len();
-    let right_len = right.len();
+/// Слияние отсортированных половин `arr[..mid]` и `arr[mid..]`.
+///
+/// Левая половина перемещается (побитово) в буфер `buf`, после чего элементы
+/// переносятся обратно в `arr`. Элементы не копируются и не клонируются, поэтому
+/// `T` не обязан реализовывать `Copy`. Длина `buf` всегда остаётся 0:
+/// буфер используется только как память, владельцем элементов остаётся `arr`.
+pub(crate) fn merge<T, F, C>(
+    arr: &mut [T],
+    mid: usize,
+    buf: &mut Vec<T>,
+    compare: &mut F,
+    collector: &mut C,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    let len = arr.len();
+    if mid == 0 || mid >= len {
+        return;
+    }
+    if buf.capacity() < mid {
+        buf.reserve(mid);
+        collector.alloc(buf.spare_capacity_mut());
+    }
 
-    let mut a = 0;
-    let mut b = 0;
+    let offset = collector.offset(arr);
+    let arr_ptr = arr.as_mut_ptr();
+    // Если `compare` запаникует, `hole` вернёт оставшиеся в буфере элементы в `arr`,
+    // и каждый элемент по-прежнему будет существовать ровно в одном экземпляре.
+    let mut hole = MergeHole {
+        buf: buf.as_mut_ptr(),
+        start: 0,
+        end: mid,
+        arr: arr_ptr,
+        dest: 0,
+    };
+    let mut right = mid;
 
-    while a < left_len && b < right_len {
-        if left[a] <= right[b] {
-            merge.push(left[a]);
-            a += 1;
-        } else {
-            merge.push(right[b]);
-            b += 1;
-        };
-    }
+    // SAFETY: `buf` вмещает `mid` элементов и не пересекается с `arr`.
+    // `hole.dest < right` пока в буфере есть элементы, поэтому запись в `dest`
+    // не затирает ещё не обработанные элементы правой половины.
+    unsafe {
+        ptr::copy_nonoverlapping(arr_ptr, hole.buf, mid);
+        for i in 0..mid {
+            collector.write(Location::Buffer(i), Location::Slice(offset + i));
+        }
 
-    for value in left.iter().skip(a) {
-        merge.push(*value);
+        while hole.start < hole.end && right < len {
+            // Берём правый элемент только если он строго меньше — так слияние стабильно.
+            let take_right =
+                compare(&*arr_ptr.add(right), &*hole.buf.add(hole.start)) == Ordering::Less;
+            let (src, from) = if take_right {
+                right += 1;
+                (arr_ptr.add(right - 1), Location::Slice(offset + right - 1))
+            } else {
+                hole.start += 1;
+                (
+                    hole.buf.add(hole.start - 1),
+                    Location::Buffer(hole.start - 1),
+                )
+            };
+            ptr::copy_nonoverlapping(src, arr_ptr.add(hole.dest), 1);
+            collector.write(Location::Slice(offset + hole.dest), from);
+            hole.dest += 1;
+        }
     }
-    for value in right.iter().skip(b) {
-        merge.push(*value);
+    // Остаток буфера (если есть) переносится в конец `arr` при удалении `hole`.
+    for (i, dest) in (hole.start..hole.end).zip(hole.dest..) {
+        collector.write(Location::Slice(offset + dest), Location::Buffer(i));
     }
+}
+
+/// Непереложенная часть буфера слияния: `buf[start..end]` должна попасть в `arr[dest..]`.
+struct MergeHole<T> {
+    buf: *mut T,
+    start: usize,
+    end: usize,
+    arr: *mut T,
+    dest: usize,
+}
 
-    arr.copy_from_slice(&merge);
+impl<T> Drop for MergeHole<T> {
+    fn drop(&mut self) {
+        // SAFETY: `buf[start..end]` содержит ещё не перенесённые элементы,
+        // а `arr[dest..dest + (end - start)]` — ровно столько свободных мест.
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.buf.add(self.start),
+                self.arr.add(self.dest),
+                self.end - self.start,
+            );
+        }
+    }
 }
 
 /// Пирамидальная сортировка (Heap Sort) — алгоритм сортировки на основе двоичной кучи.
@@ -318,7 +722,53 @@ pub fn merge_sort<T: Ord + Copy + Debug>(arr: &mut [T]) {
 /// small - time:   [10.477 ns 10.494 ns 10.513 ns]
 /// big - time:   [9.6989 µs 9.7217 µs 9.7452 µs]
 ///
-pub fn heap_sort<T: Ord + Copy + Debug>(arr: &mut [T]) {
+pub fn heap_sort<T: Ord>(arr: &mut [T]) {
+    heap_sort_by(arr, T::cmp);
+}
+
+/// Пирамидальная сортировка с пользовательской функцией сравнения.
+/// Аналог [`heap_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::heap_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// heap_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn heap_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    heap_sort_with(arr, compare, &mut ());
+}
+
+/// Пирамидальная сортировка по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::heap_sort_by_key;
+///
+/// let mut arr = ["ccc", "a", "bb"];
+/// heap_sort_by_key(&mut arr, |a| a.len());
+/// assert_eq!(arr, ["a", "bb", "ccc"]);
+/// ```
+pub fn heap_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    heap_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn heap_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
     let len = arr.len();
     if len <= 1 {
         return;
@@ -326,33 +776,177 @@ pub fn heap_sort<T: Ord + Copy + Debug>(arr: &mut [T]) {
 
     // 1. Строим максимальную кучу (max-heap)
     for i in (0..len / 2).rev() {
-        heapify(arr, len, i);
+        heapify(arr, len, i, &mut compare, collector);
     }
 
     // 2. Извлекаем элементы из кучи по одному
     for i in (1..len).rev() {
-        arr.swap(0, i); // Перемещаем текущий корень в конец
-        heapify(arr, i, 0); // Восстанавливаем свойство кучи
+        swap(arr, 0, i, collector); // Перемещаем текущий корень в конец
+        heapify(arr, i, 0, &mut compare, collector); // Восстанавливаем свойство кучи
     }
 }
 
-fn heapify<T: Ord + Debug>(arr: &mut [T], heap_size: usize, root: usize) {
+pub(crate) fn heapify<T, F, C>(
+    arr: &mut [T],
+    heap_size: usize,
+    root: usize,
+    compare: &mut F,
+    collector: &mut C,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
     let mut largest = root;
     let left = 2 * root + 1;
     let right = 2 * root + 2;
 
     // Находим наибольший элемент среди корня и его потомков
-    if left < heap_size && arr[left] > arr[largest] {
+    if left < heap_size && compare(&arr[left], &arr[largest]) == Ordering::Greater {
         largest = left;
     }
-    if right < heap_size && arr[right] > arr[largest] {
+    if right < heap_size && compare(&arr[right], &arr[largest]) == Ordering::Greater {
         largest = right;
     }
 
     // Если наибольший элемент не корень, меняем их местами
     if largest != root {
-        arr.swap(root, largest);
+        swap(arr, root, largest, collector);
         // Рекурсивно восстанавливаем свойство кучи для поддерева
-        heapify(arr, heap_size, largest);
+        heapify(arr, heap_size, largest, compare, collector);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, panic};
+
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    /// Общее состояние для [`DropCounter`]: число удалений и оставшийся лимит сравнений.
+    struct Stats {
+        drops: Cell<usize>,
+        compare_budget: Cell<usize>,
+    }
+
+    impl Stats {
+        fn new(compare_budget: usize) -> Self {
+            Self {
+                drops: Cell::new(0),
+                compare_budget: Cell::new(compare_budget),
+            }
+        }
+    }
+
+    /// Элемент без `Copy` и `Debug`, считающий свои удаления.
+    /// Сравнение паникует, когда лимит сравнений исчерпан.
+    struct DropCounter<'a> {
+        value: u32,
+        stats: &'a Stats,
+    }
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.stats.drops.set(self.stats.drops.get() + 1);
+        }
+    }
+
+    impl PartialEq for DropCounter<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.cmp(other) == Ordering::Equal
+        }
+    }
+
+    impl Eq for DropCounter<'_> {}
+
+    impl PartialOrd for DropCounter<'_> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for DropCounter<'_> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            let budget = self.stats.compare_budget.get();
+            assert!(budget > 0, "лимит сравнений исчерпан");
+            self.stats.compare_budget.set(budget - 1);
+            self.value.cmp(&other.value)
+        }
+    }
+
+    fn values(len: usize) -> Vec<u32> {
+        let mut rng = StdRng::seed_from_u64(0x2545_f491);
+        (0..len).map(|_| rng.random_range(0..100)).collect()
+    }
+
+    fn check_sort(sort: impl Fn(&mut [DropCounter])) {
+        let values = values(1000);
+        let mut expected = values.clone();
+        expected.sort();
+
+        let stats = Stats::new(usize::MAX);
+        let mut arr: Vec<_> = values
+            .iter()
+            .map(|&value| DropCounter {
+                value,
+                stats: &stats,
+            })
+            .collect();
+        sort(&mut arr);
+        assert_eq!(stats.drops.get(), 0);
+        assert_eq!(arr.iter().map(|c| c.value).collect::<Vec<_>>(), expected);
+        drop(arr);
+        assert_eq!(stats.drops.get(), values.len());
+
+        // Паника в сравнении не должна терять или дублировать элементы.
+        let stats = Stats::new(2000);
+        let mut arr: Vec<_> = values
+            .iter()
+            .map(|&value| DropCounter {
+                value,
+                stats: &stats,
+            })
+            .collect();
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| sort(&mut arr)));
+        assert!(result.is_err());
+        assert_eq!(stats.drops.get(), 0);
+        let mut rest: Vec<_> = arr.iter().map(|c| c.value).collect();
+        rest.sort();
+        assert_eq!(rest, expected);
+        drop(arr);
+        assert_eq!(stats.drops.get(), values.len());
+    }
+
+    #[test]
+    fn test_quick_sort_moves() {
+        check_sort(|arr| quick_sort(arr));
+    }
+
+    #[test]
+    fn test_merge_sort_moves() {
+        check_sort(|arr| merge_sort(arr));
+    }
+
+    #[test]
+    fn test_heap_sort_moves() {
+        check_sort(|arr| heap_sort(arr));
+    }
+
+    #[test]
+    fn test_sort_strings() {
+        let words = ["груша", "яблоко", "абрикос", "вишня", "банан", "абрикос"];
+        let mut expected: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        expected.sort();
+
+        for sort in [
+            quick_sort::<String>,
+            merge_sort::<String>,
+            heap_sort::<String>,
+        ] {
+            let mut arr: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+            sort(&mut arr);
+            assert_eq!(arr, expected);
+        }
     }
 }
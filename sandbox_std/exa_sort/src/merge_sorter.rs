@@ -0,0 +1,309 @@
+//! Сортировка слиянием с переиспользуемым буфером.
+//!
+//! [`crate::merge_sort`] выделяет буфер на каждый вызов. [`MergeSorter`] хранит буфер между
+//! вызовами, поэтому при сортировке множества срезов память выделяется только при росте
+//! максимального размера. Доступны три режима ([`MergeMode`]):
+//!  * [`MergeMode::TopDown`] — классическая рекурсивная сортировка;
+//!  * [`MergeMode::BottomUp`] — итеративная, без рекурсии: сливаются блоки длиной 1, 2, 4, ...;
+//!  * [`MergeMode::Natural`] — ищет уже упорядоченные участки (серии), разворачивает
+//!    убывающие, дополняет короткие серии вставками до [`MIN_RUN`] и сливает соседние серии.
+//!    На частично отсортированных данных работает близко к TimSort, на отсортированных — O(n).
+//!
+//! Все режимы стабильны.
+
+use std::cmp::Ordering;
+
+use crate::{
+    instrument::{Collector, reverse, swap},
+    merge, merge_sort_helper,
+};
+
+/// Минимальная длина серии в режиме [`MergeMode::Natural`].
+pub const MIN_RUN: usize = 32;
+
+/// Режим работы [`MergeSorter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeMode {
+    /// Рекурсивное деление пополам.
+    #[default]
+    TopDown,
+    /// Итеративное слияние блоков удваивающейся длины.
+    BottomUp,
+    /// Слияние естественных серий.
+    Natural,
+}
+
+/// Сортировка слиянием с переиспользуемым буфером.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::{MergeMode, MergeSorter};
+///
+/// let mut sorter = MergeSorter::new(MergeMode::Natural);
+///
+/// let mut arr = [1, 2, 3, 9, 8, 7, 4, 5, 6];
+/// sorter.sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// sorter.sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+///
+/// ```
+/// use exa_sort::{MergeMode, MergeSorter};
+///
+/// let mut sorter = MergeSorter::new(MergeMode::BottomUp);
+///
+/// let mut arr = [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+/// sorter.sort_by_key(&mut arr, |&(n, _)| n);
+/// assert_eq!(arr, [(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+/// ```
+pub struct MergeSorter<T> {
+    mode: MergeMode,
+    /// Память для слияния. Длина всегда 0: элементы хранятся в нём только во время слияния.
+    buf: Vec<T>,
+}
+
+impl<T> MergeSorter<T> {
+    /// Создаёт сортировщик без предварительно выделенного буфера.
+    pub fn new(mode: MergeMode) -> Self {
+        Self::with_capacity(mode, 0)
+    }
+
+    /// Создаёт сортировщик с буфером, достаточным для срезов длиной до `2 * capacity`.
+    pub fn with_capacity(mode: MergeMode, capacity: usize) -> Self {
+        Self {
+            mode,
+            buf: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Текущий режим.
+    pub fn mode(&self) -> MergeMode {
+        self.mode
+    }
+
+    /// Меняет режим, сохраняя выделенный буфер.
+    pub fn set_mode(&mut self, mode: MergeMode) {
+        self.mode = mode;
+    }
+
+    /// Ёмкость буфера слияния.
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Сортирует срез по возрастанию.
+    pub fn sort(&mut self, arr: &mut [T])
+    where
+        T: Ord,
+    {
+        self.sort_by(arr, T::cmp);
+    }
+
+    /// Сортирует срез, порядок элементов задаёт `compare`.
+    pub fn sort_by<F>(&mut self, arr: &mut [T], compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.sort_with(arr, compare, &mut ());
+    }
+
+    /// Сортирует срез по ключу, который вычисляет `key`.
+    pub fn sort_by_key<K, F>(&mut self, arr: &mut [T], mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(arr, |a, b| key(a).cmp(&key(b)));
+    }
+
+    pub(crate) fn sort_with<F, C>(&mut self, arr: &mut [T], mut compare: F, collector: &mut C)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        C: Collector<T>,
+    {
+        let buf = &mut self.buf;
+        match self.mode {
+            MergeMode::TopDown => merge_sort_helper(arr, buf, &mut compare, collector),
+            MergeMode::BottomUp => bottom_up(arr, buf, &mut compare, collector),
+            MergeMode::Natural => natural(arr, buf, &mut compare, collector),
+        }
+    }
+}
+
+impl<T> Default for MergeSorter<T> {
+    fn default() -> Self {
+        Self::new(MergeMode::default())
+    }
+}
+
+/// Итеративная сортировка слиянием: сливаются соседние блоки длиной `width`,
+/// после чего `width` удваивается.
+fn bottom_up<T, F, C>(arr: &mut [T], buf: &mut Vec<T>, compare: &mut F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    let len = arr.len();
+    let mut width = 1;
+    while width < len {
+        for chunk in arr.chunks_mut(2 * width) {
+            merge(chunk, width, buf, compare, collector);
+        }
+        width *= 2;
+    }
+}
+
+/// Естественная сортировка слиянием.
+fn natural<T, F, C>(arr: &mut [T], buf: &mut Vec<T>, compare: &mut F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    let mut runs = find_runs(arr, compare, collector);
+
+    // Сливаем соседние серии попарно, пока не останется одна.
+    while runs.len() > 2 {
+        let mut merged = Vec::with_capacity(runs.len() / 2 + 1);
+        merged.push(0);
+        for pair in runs[1..].chunks(2) {
+            let start = *merged.last().expect("есть начало серии");
+            if let [mid, end] = *pair {
+                merge(&mut arr[start..end], mid - start, buf, compare, collector);
+                merged.push(end);
+            } else {
+                merged.push(pair[0]);
+            }
+        }
+        runs = merged;
+    }
+}
+
+/// Разбивает срез на упорядоченные серии. Убывающие серии разворачиваются,
+/// короткие дополняются вставками до [`MIN_RUN`].
+/// Возвращает границы серий: `0 = runs[0] < runs[1] < ... < runs[k] = len`.
+fn find_runs<T, F, C>(arr: &mut [T], compare: &mut F, collector: &mut C) -> Vec<usize>
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    let len = arr.len();
+    let mut runs = vec![0];
+    let mut start = 0;
+
+    while start < len {
+        let mut end = start + 1;
+        if end < len {
+            let descending = compare(&arr[end], &arr[start]) == Ordering::Less;
+            end += 1;
+            if descending {
+                // Только строго убывающая серия: разворот не должен менять порядок равных.
+                while end < len && compare(&arr[end], &arr[end - 1]) == Ordering::Less {
+                    end += 1;
+                }
+                reverse(&mut arr[start..end], collector);
+            } else {
+                while end < len && compare(&arr[end], &arr[end - 1]) != Ordering::Less {
+                    end += 1;
+                }
+            }
+        }
+
+        let min_end = len.min(start + MIN_RUN);
+        while end < min_end {
+            insert_tail(&mut arr[start..=end], compare, collector);
+            end += 1;
+        }
+
+        runs.push(end);
+        start = end;
+    }
+
+    runs
+}
+
+/// Вставляет последний элемент среза на место в отсортированной части `arr[..len - 1]`.
+/// Элемент проходит только мимо строго больших, поэтому вставка стабильна.
+fn insert_tail<T, F, C>(arr: &mut [T], compare: &mut F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    let mut i = arr.len() - 1;
+    while i > 0 && compare(&arr[i], &arr[i - 1]) == Ordering::Less {
+        swap(arr, i, i - 1, collector);
+        i -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    const MODES: [MergeMode; 3] = [MergeMode::TopDown, MergeMode::BottomUp, MergeMode::Natural];
+
+    /// Пары (ключ, исходная позиция) для проверки стабильности.
+    fn pairs(len: usize) -> Vec<(u32, usize)> {
+        let mut rng = StdRng::seed_from_u64(0x9e37_79b9);
+        (0..len).map(|i| (rng.random_range(0..50), i)).collect()
+    }
+
+    #[test]
+    fn test_stable() {
+        for mode in MODES {
+            let mut sorter = MergeSorter::new(mode);
+            for len in [0, 1, 2, 31, 32, 33, 100, 1000] {
+                let mut arr = pairs(len);
+                let mut expected = arr.clone();
+                expected.sort_by_key(|&(key, _)| key);
+                sorter.sort_by_key(&mut arr, |&(key, _)| key);
+                assert_eq!(arr, expected, "{mode:?}, len = {len}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_partially_sorted() {
+        // Возрастающая, убывающая и снова возрастающая серия.
+        let mut arr: Vec<u32> = (500..1000)
+            .chain((0..500).rev())
+            .chain(1000..1500)
+            .collect();
+        let expected: Vec<u32> = (0..1500).collect();
+        for mode in MODES {
+            let mut arr = arr.clone();
+            MergeSorter::new(mode).sort(&mut arr);
+            assert_eq!(arr, expected, "{mode:?}");
+        }
+        assert_eq!(
+            find_runs(&mut arr, &mut u32::cmp, &mut ()),
+            [0, 500, 1000, 1500]
+        );
+    }
+
+    #[test]
+    fn test_reuses_buffer() {
+        let mut sorter = MergeSorter::with_capacity(MergeMode::BottomUp, 512);
+        let capacity = sorter.capacity();
+        for _ in 0..10 {
+            let mut arr: Vec<u32> = (0..1024).rev().collect();
+            sorter.sort(&mut arr);
+            assert!(arr.is_sorted());
+        }
+        assert_eq!(sorter.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_strings() {
+        let mut arr: Vec<String> = ["груша", "яблоко", "абрикос", "вишня"]
+            .map(String::from)
+            .into();
+        MergeSorter::new(MergeMode::Natural).sort(&mut arr);
+        assert_eq!(arr, ["абрикос", "вишня", "груша", "яблоко"]);
+    }
+}
@@ -0,0 +1,316 @@
+//! Параллельная сортировка на потоках `std::thread::scope`.
+//!
+//! Срез рекурсивно делится на части, и одна из частей обрабатывается в отдельном потоке.
+//! На каждом уровне количество доступных потоков делится пополам, поэтому одновременно
+//! работает не больше [`ParallelConfig::threads`] потоков. Части короче
+//! [`ParallelConfig::cutoff`] сортируются последовательно: запуск потока для них дороже,
+//! чем сама сортировка.
+//!
+//! Функция сравнения вызывается из нескольких потоков, поэтому должна быть `Fn + Sync`.
+
+use std::{cmp::Ordering, num::NonZeroUsize, thread};
+
+use crate::{
+    intro_sort::{choose_pivot, hoare_partition},
+    intro_sort_by, merge, merge_sort_helper,
+};
+
+/// Длина части, начиная с которой сортировка выполняется последовательно, по умолчанию.
+pub const DEFAULT_CUTOFF: usize = 1 << 14;
+
+/// Настройки параллельной сортировки.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelConfig {
+    /// Максимальное количество потоков (включая текущий).
+    pub threads: usize,
+    /// Части не длиннее `cutoff` сортируются в одном потоке.
+    pub cutoff: usize,
+}
+
+impl ParallelConfig {
+    /// Создаёт настройки с заданным количеством потоков и порогом.
+    pub fn new(threads: usize, cutoff: usize) -> Self {
+        Self { threads, cutoff }
+    }
+
+    /// Параллельная сортировка слиянием. Сортировка стабильна.
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// use exa_sort::ParallelConfig;
+    ///
+    /// let mut arr: Vec<u32> = (0..100_000).rev().collect();
+    /// ParallelConfig::new(4, 1000).merge_sort_by(&mut arr, |a, b| a.cmp(b));
+    /// assert!(arr.is_sorted());
+    /// ```
+    pub fn merge_sort_by<T, F>(&self, arr: &mut [T], compare: F)
+    where
+        T: Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        parallel_merge(arr, self.threads, self.cutoff.max(1), &compare);
+    }
+
+    /// Параллельная быстрая сортировка. Последовательные части сортируются
+    /// [`crate::intro_sort_by`], поэтому худший случай — O(n log n). Сортировка нестабильна.
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// use exa_sort::ParallelConfig;
+    ///
+    /// let mut arr: Vec<u32> = (0..100_000).map(|i| i % 100).collect();
+    /// ParallelConfig::new(4, 1000).quick_sort_by(&mut arr, |a, b| b.cmp(a));
+    /// assert!(arr.is_sorted_by(|a, b| a >= b));
+    /// ```
+    pub fn quick_sort_by<T, F>(&self, arr: &mut [T], compare: F)
+    where
+        T: Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        parallel_quick(arr, self.threads, self.cutoff.max(1), &compare);
+    }
+}
+
+impl Default for ParallelConfig {
+    /// Все доступные ядра и порог [`DEFAULT_CUTOFF`].
+    fn default() -> Self {
+        let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        Self::new(threads, DEFAULT_CUTOFF)
+    }
+}
+
+/// Параллельная сортировка слиянием (Parallel Merge Sort)
+/// Использует все доступные ядра, см. [`ParallelConfig`].
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::parallel_merge_sort;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// parallel_merge_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+pub fn parallel_merge_sort<T: Ord + Send>(arr: &mut [T]) {
+    parallel_merge_sort_by(arr, T::cmp);
+}
+
+/// Параллельная сортировка слиянием с пользовательской функцией сравнения.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::parallel_merge_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// parallel_merge_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn parallel_merge_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    ParallelConfig::default().merge_sort_by(arr, compare);
+}
+
+/// Параллельная сортировка слиянием по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::parallel_merge_sort_by_key;
+///
+/// let mut arr = [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+/// parallel_merge_sort_by_key(&mut arr, |&(n, _)| n);
+/// assert_eq!(arr, [(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+/// ```
+pub fn parallel_merge_sort_by_key<T, K, F>(arr: &mut [T], key: F)
+where
+    T: Send,
+    K: Ord,
+    F: Fn(&T) -> K + Sync,
+{
+    parallel_merge_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Параллельная быстрая сортировка (Parallel Quick Sort)
+/// Использует все доступные ядра, см. [`ParallelConfig`].
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::parallel_quick_sort;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// parallel_quick_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+pub fn parallel_quick_sort<T: Ord + Send>(arr: &mut [T]) {
+    parallel_quick_sort_by(arr, T::cmp);
+}
+
+/// Параллельная быстрая сортировка с пользовательской функцией сравнения.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::parallel_quick_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// parallel_quick_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn parallel_quick_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    ParallelConfig::default().quick_sort_by(arr, compare);
+}
+
+/// Параллельная быстрая сортировка по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::parallel_quick_sort_by_key;
+///
+/// let mut arr = ["ccc", "a", "bb"];
+/// parallel_quick_sort_by_key(&mut arr, |a| a.len());
+/// assert_eq!(arr, ["a", "bb", "ccc"]);
+/// ```
+pub fn parallel_quick_sort_by_key<T, K, F>(arr: &mut [T], key: F)
+where
+    T: Send,
+    K: Ord,
+    F: Fn(&T) -> K + Sync,
+{
+    parallel_quick_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+fn parallel_merge<T, F>(arr: &mut [T], threads: usize, cutoff: usize, compare: &F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let len = arr.len();
+    if threads <= 1 || len <= cutoff {
+        let mut buf = Vec::with_capacity(len / 2);
+        merge_sort_helper(arr, &mut buf, &mut |a, b| compare(a, b), &mut ());
+        return;
+    }
+
+    let mid = len / 2;
+    let (left, right) = arr.split_at_mut(mid);
+    let spawned = threads / 2;
+    thread::scope(|scope| {
+        scope.spawn(|| parallel_merge(left, spawned, cutoff, compare));
+        parallel_merge(right, threads - spawned, cutoff, compare);
+    });
+
+    let mut buf = Vec::with_capacity(mid);
+    merge(arr, mid, &mut buf, &mut |a, b| compare(a, b), &mut ());
+}
+
+fn parallel_quick<T, F>(arr: &mut [T], threads: usize, cutoff: usize, compare: &F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let len = arr.len();
+    if threads <= 1 || len <= cutoff {
+        intro_sort_by(arr, compare);
+        return;
+    }
+
+    let mut compare_mut = |a: &T, b: &T| compare(a, b);
+    let pivot = choose_pivot(arr, &mut compare_mut);
+    arr.swap(0, pivot);
+    let mid = hoare_partition(arr, &mut compare_mut, &mut ());
+
+    let (left, right) = arr.split_at_mut(mid);
+    let right = &mut right[1..];
+    // Потоки распределяем пропорционально размерам частей.
+    let spawned = (threads * left.len() / (len - 1)).clamp(1, threads - 1);
+    thread::scope(|scope| {
+        scope.spawn(|| parallel_quick(left, spawned, cutoff, compare));
+        parallel_quick(right, threads - spawned, cutoff, compare);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn values(len: usize) -> Vec<u32> {
+        let mut rng = StdRng::seed_from_u64(0x2545_f491);
+        (0..len).map(|_| rng.random_range(0..1 << 24)).collect()
+    }
+
+    #[test]
+    fn test_threads_and_cutoff() {
+        let values = values(200_000);
+        let mut expected = values.clone();
+        expected.sort();
+
+        for threads in [0, 1, 2, 3, 8] {
+            for cutoff in [0, 1000, 1_000_000] {
+                let config = ParallelConfig::new(threads, cutoff);
+
+                let mut arr = values.clone();
+                config.merge_sort_by(&mut arr, u32::cmp);
+                assert_eq!(arr, expected, "merge: {config:?}");
+
+                let mut arr = values.clone();
+                config.quick_sort_by(&mut arr, u32::cmp);
+                assert_eq!(arr, expected, "quick: {config:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_stable() {
+        let mut arr: Vec<(u32, usize)> = values(100_000)
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (v % 10, i))
+            .collect();
+        let mut expected = arr.clone();
+        expected.sort_by_key(|&(key, _)| key);
+        ParallelConfig::new(4, 100).merge_sort_by(&mut arr, |a, b| a.0.cmp(&b.0));
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_sorted_and_equal() {
+        let config = ParallelConfig::new(4, 1000);
+
+        let mut arr: Vec<u32> = (0..1_000_000).collect();
+        config.quick_sort_by(&mut arr, u32::cmp);
+        assert!(arr.is_sorted());
+
+        let mut arr = vec![1u8; 1_000_000];
+        config.quick_sort_by(&mut arr, u8::cmp);
+        assert!(arr.iter().all(|&v| v == 1));
+    }
+
+    #[test]
+    fn test_strings() {
+        let mut arr: Vec<String> = values(10_000).iter().map(u32::to_string).collect();
+        let mut expected = arr.clone();
+        expected.sort();
+        let mut copy = arr.clone();
+        parallel_merge_sort(&mut copy);
+        assert_eq!(copy, expected);
+
+        parallel_quick_sort(&mut arr);
+        assert_eq!(arr, expected);
+    }
+}
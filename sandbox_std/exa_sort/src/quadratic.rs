@@ -0,0 +1,431 @@
+//! Улучшения простых сортировок O(n²).
+//!
+//! Алгоритмы из того же семейства, что [`crate::bubble_sort`], [`crate::selection_sort`]
+//! и [`crate::insertion_sort`]:
+//!  * [`comb_sort`] — пузырёк с уменьшающимся шагом, быстро убирает «черепах»
+//!    (маленькие элементы в конце массива);
+//!  * [`cocktail_shaker_sort`] — пузырёк, проходящий массив в обе стороны;
+//!  * [`gnome_sort`] — сортировка вставками, записанная одним циклом;
+//!  * [`binary_insertion_sort`] — сортировка вставками с двоичным поиском места вставки.
+
+use std::cmp::Ordering;
+
+use crate::instrument::{Collector, rotate_right, swap};
+
+/// Коэффициент уменьшения шага сортировки расчёской.
+const COMB_SHRINK: f64 = 1.3;
+
+/// Сортировка расчёской (Comb Sort)
+/// Как сортировка пузырьком, но сравниваются элементы на расстоянии `gap`,
+/// который на каждом проходе уменьшается в 1.3 раза. Средняя сложность близка
+/// к O(n log n), худшая — O(n²). Нестабильна.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::comb_sort;
+///
+/// let mut arr = [2, 3, 1];
+/// comb_sort(&mut arr);
+/// assert_eq!(arr, [1, 2 ,3]);
+/// ```
+///
+/// ```
+/// use exa_sort::comb_sort;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// comb_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_sort::comb_sort;
+///
+/// let mut arr = [10, 4, 7, 3, 1, 8, 2, 9, 5, 6, 15, 12, 18, 11, 14, 17, 13, 16, 19, 1];
+/// comb_sort(&mut arr);
+/// assert_eq!(arr, [1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
+/// ```
+pub fn comb_sort<T: Ord>(arr: &mut [T]) {
+    comb_sort_by(arr, T::cmp);
+}
+
+/// Сортировка расчёской с пользовательской функцией сравнения.
+/// Аналог [`comb_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::comb_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// comb_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn comb_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    comb_sort_with(arr, compare, &mut ());
+}
+
+/// Сортировка расчёской по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::comb_sort_by_key;
+///
+/// let mut arr = ["ccc", "a", "bb"];
+/// comb_sort_by_key(&mut arr, |a| a.len());
+/// assert_eq!(arr, ["a", "bb", "ccc"]);
+/// ```
+pub fn comb_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    comb_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn comb_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    let len = arr.len();
+    let mut gap = len;
+    let mut swapped = true;
+
+    while gap > 1 || swapped {
+        gap = ((gap as f64 / COMB_SHRINK) as usize).max(1);
+        swapped = false;
+        for i in 0..len.saturating_sub(gap) {
+            if compare(&arr[i], &arr[i + gap]) == Ordering::Greater {
+                swap(arr, i, i + gap, collector);
+                swapped = true;
+            }
+        }
+    }
+}
+
+/// Шейкерная сортировка (Cocktail Shaker Sort)
+/// Сортировка пузырьком, проходящая массив попеременно слева направо и справа налево.
+/// Границы сужаются до места последнего обмена, поэтому на почти отсортированных
+/// массивах работает за O(n). Худшая сложность O(n²). Стабильна.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::cocktail_shaker_sort;
+///
+/// let mut arr = [2, 3, 1];
+/// cocktail_shaker_sort(&mut arr);
+/// assert_eq!(arr, [1, 2 ,3]);
+/// ```
+///
+/// ```
+/// use exa_sort::cocktail_shaker_sort;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// cocktail_shaker_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_sort::cocktail_shaker_sort;
+///
+/// let mut arr = [10, 4, 7, 3, 1, 8, 2, 9, 5, 6, 15, 12, 18, 11, 14, 17, 13, 16, 19, 1];
+/// cocktail_shaker_sort(&mut arr);
+/// assert_eq!(arr, [1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
+/// ```
+pub fn cocktail_shaker_sort<T: Ord>(arr: &mut [T]) {
+    cocktail_shaker_sort_by(arr, T::cmp);
+}
+
+/// Шейкерная сортировка с пользовательской функцией сравнения.
+/// Аналог [`cocktail_shaker_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::cocktail_shaker_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// cocktail_shaker_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn cocktail_shaker_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    cocktail_shaker_sort_with(arr, compare, &mut ());
+}
+
+/// Шейкерная сортировка по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::cocktail_shaker_sort_by_key;
+///
+/// let mut arr = [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+/// cocktail_shaker_sort_by_key(&mut arr, |&(n, _)| n);
+/// assert_eq!(arr, [(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+/// ```
+pub fn cocktail_shaker_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    cocktail_shaker_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn cocktail_shaker_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    // Неотсортированная часть — `arr[start..end]`.
+    let mut start = 0;
+    let mut end = arr.len();
+
+    while end - start > 1 {
+        let mut last_swap = start;
+        for i in start..end - 1 {
+            if compare(&arr[i], &arr[i + 1]) == Ordering::Greater {
+                swap(arr, i, i + 1, collector);
+                last_swap = i + 1;
+            }
+        }
+        end = last_swap;
+        if end - start <= 1 {
+            break;
+        }
+
+        last_swap = end;
+        for i in (start..end - 1).rev() {
+            if compare(&arr[i], &arr[i + 1]) == Ordering::Greater {
+                swap(arr, i, i + 1, collector);
+                last_swap = i;
+            }
+        }
+        start = last_swap;
+    }
+}
+
+/// Гномья сортировка (Gnome Sort)
+/// Гном смотрит на текущий и предыдущий элементы: если они в правильном порядке,
+/// шагает вперёд, иначе меняет их местами и шагает назад. По сути — сортировка
+/// вставками без вложенного цикла. Сложность O(n²), на отсортированном массиве O(n).
+/// Стабильна.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::gnome_sort;
+///
+/// let mut arr = [2, 3, 1];
+/// gnome_sort(&mut arr);
+/// assert_eq!(arr, [1, 2 ,3]);
+/// ```
+///
+/// ```
+/// use exa_sort::gnome_sort;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// gnome_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_sort::gnome_sort;
+///
+/// let mut arr = [10, 4, 7, 3, 1, 8, 2, 9, 5, 6, 15, 12, 18, 11, 14, 17, 13, 16, 19, 1];
+/// gnome_sort(&mut arr);
+/// assert_eq!(arr, [1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
+/// ```
+pub fn gnome_sort<T: Ord>(arr: &mut [T]) {
+    gnome_sort_by(arr, T::cmp);
+}
+
+/// Гномья сортировка с пользовательской функцией сравнения.
+/// Аналог [`gnome_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::gnome_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// gnome_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn gnome_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    gnome_sort_with(arr, compare, &mut ());
+}
+
+/// Гномья сортировка по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::gnome_sort_by_key;
+///
+/// let mut arr = ["ccc", "a", "bb"];
+/// gnome_sort_by_key(&mut arr, |a| a.len());
+/// assert_eq!(arr, ["a", "bb", "ccc"]);
+/// ```
+pub fn gnome_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    gnome_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn gnome_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    let mut pos = 1;
+    while pos < arr.len() {
+        if pos == 0 || compare(&arr[pos - 1], &arr[pos]) != Ordering::Greater {
+            pos += 1;
+        } else {
+            swap(arr, pos - 1, pos, collector);
+            pos -= 1;
+        }
+    }
+}
+
+/// Сортировка двоичными вставками (Binary Insertion Sort)
+/// Место вставки ищется двоичным поиском, поэтому сравнений O(n log n),
+/// но перемещений по-прежнему O(n²). Выгодна, когда сравнение дороже перемещения.
+/// Стабильна.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::binary_insertion_sort;
+///
+/// let mut arr = [2, 3, 1];
+/// binary_insertion_sort(&mut arr);
+/// assert_eq!(arr, [1, 2 ,3]);
+/// ```
+///
+/// ```
+/// use exa_sort::binary_insertion_sort;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// binary_insertion_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_sort::binary_insertion_sort;
+///
+/// let mut arr = [10, 4, 7, 3, 1, 8, 2, 9, 5, 6, 15, 12, 18, 11, 14, 17, 13, 16, 19, 1];
+/// binary_insertion_sort(&mut arr);
+/// assert_eq!(arr, [1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
+/// ```
+pub fn binary_insertion_sort<T: Ord>(arr: &mut [T]) {
+    binary_insertion_sort_by(arr, T::cmp);
+}
+
+/// Сортировка двоичными вставками с пользовательской функцией сравнения.
+/// Аналог [`binary_insertion_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::binary_insertion_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// binary_insertion_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn binary_insertion_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    binary_insertion_sort_with(arr, compare, &mut ());
+}
+
+/// Сортировка двоичными вставками по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::binary_insertion_sort_by_key;
+///
+/// let mut arr = [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+/// binary_insertion_sort_by_key(&mut arr, |&(n, _)| n);
+/// assert_eq!(arr, [(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+/// ```
+pub fn binary_insertion_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    binary_insertion_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn binary_insertion_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    for current in 1..arr.len() {
+        let (sorted, rest) = arr.split_at(current);
+        // Вставляем после всех элементов, не больших текущего, — так сортировка стабильна.
+        let pos = sorted.partition_point(|value| compare(value, &rest[0]) != Ordering::Greater);
+        rotate_right(&mut arr[pos..=current], collector);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type SortBy = fn(&mut [(u32, usize)], fn(&(u32, usize), &(u32, usize)) -> Ordering);
+
+    const SORTS: [(&str, SortBy, bool); 4] = [
+        ("comb", |arr, compare| comb_sort_by(arr, compare), false),
+        (
+            "cocktail",
+            |arr, compare| cocktail_shaker_sort_by(arr, compare),
+            true,
+        ),
+        ("gnome", |arr, compare| gnome_sort_by(arr, compare), true),
+        (
+            "binary_insertion",
+            |arr, compare| binary_insertion_sort_by(arr, compare),
+            true,
+        ),
+    ];
+
+    #[test]
+    fn test_sorts() {
+        for len in [0, 1, 2, 3, 10, 500] {
+            let values: Vec<(u32, usize)> = (0..len)
+                .map(|i| ((i as u32).wrapping_mul(2_654_435_761) % 20, i))
+                .collect();
+            let mut expected = values.clone();
+            expected.sort_by_key(|&(key, _)| key);
+
+            for (name, sort, stable) in SORTS {
+                let mut arr = values.clone();
+                sort(&mut arr, |a, b| a.0.cmp(&b.0));
+                if stable {
+                    assert_eq!(arr, expected, "{name}, len = {len}");
+                } else {
+                    assert!(arr.is_sorted_by_key(|&(key, _)| key), "{name}, len = {len}");
+                }
+            }
+        }
+    }
+}
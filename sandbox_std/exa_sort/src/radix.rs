@@ -0,0 +1,341 @@
+//! Поразрядная сортировка (Radix Sort).
+//!
+//! Не сравнивает элементы между собой, а раскладывает их по «корзинам» по значению
+//! очередного байта ключа. Сложность O(w · n), где `w` — длина ключа в байтах.
+//!  * LSD (least significant digit) — проход от младшего байта к старшему, каждый проход
+//!    стабилен. Требует дополнительный буфер размером с массив.
+//!  * MSD (most significant digit) — от старшего байта к младшему, корзины сортируются
+//!    рекурсивно. Реализована как American Flag Sort: элементы переставляются на месте,
+//!    поэтому подходит и для строк, которые нельзя копировать.
+//!
+//! Знаковые числа сортируются инвертированием старшего бита: после этого беззнаковый
+//! порядок ключей совпадает с порядком чисел.
+
+use std::cmp::Ordering;
+
+use crate::insertion_sort_by;
+
+/// Количество корзин для MSD: 256 значений байта и корзина «ключ закончился».
+const BUCKETS: usize = 257;
+
+/// Корзины не длиннее этого значения досортировываются вставками.
+const MSD_INSERTION_THRESHOLD: usize = 32;
+
+/// Целочисленный ключ для поразрядной сортировки.
+pub trait RadixKey: Copy + Ord {
+    /// Размер ключа в байтах.
+    const BYTES: usize;
+
+    /// Байт ключа с номером `index` (0 — младший).
+    fn key_byte(self, index: usize) -> u8;
+
+    /// Ключ как беззнаковое число, порядок которого совпадает с порядком значений.
+    fn key(self) -> u128;
+}
+
+macro_rules! impl_radix_key_unsigned {
+    ($($t:ty),*) => {$(
+        impl RadixKey for $t {
+            const BYTES: usize = size_of::<$t>();
+
+            #[inline]
+            fn key_byte(self, index: usize) -> u8 {
+                (self >> (index * 8)) as u8
+            }
+
+            #[inline]
+            fn key(self) -> u128 {
+                self as u128
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_radix_key_signed {
+    ($($t:ty => $u:ty),*) => {$(
+        impl RadixKey for $t {
+            const BYTES: usize = size_of::<$t>();
+
+            #[inline]
+            fn key_byte(self, index: usize) -> u8 {
+                ((self as $u ^ (1 << (<$u>::BITS - 1))) >> (index * 8)) as u8
+            }
+
+            #[inline]
+            fn key(self) -> u128 {
+                (self as $u ^ (1 << (<$u>::BITS - 1))) as u128
+            }
+        }
+    )*};
+}
+
+impl_radix_key_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_radix_key_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+/// Поразрядная сортировка LSD (LSD Radix Sort)
+/// Стабильна, сложность O(w · n), память O(n). Проходы, в которых у всех элементов
+/// одинаковый байт, пропускаются.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::radix_sort_lsd;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// radix_sort_lsd(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_sort::radix_sort_lsd;
+///
+/// let mut arr = [300i16, -1, 0, i16::MIN, 7, i16::MAX, -300];
+/// radix_sort_lsd(&mut arr);
+/// assert_eq!(arr, [i16::MIN, -300, -1, 0, 7, 300, i16::MAX]);
+/// ```
+pub fn radix_sort_lsd<T: RadixKey>(arr: &mut [T]) {
+    if arr.len() <= 1 {
+        return;
+    }
+
+    let mut buf = arr.to_vec();
+    let mut in_buf = false;
+    for index in 0..T::BYTES {
+        let moved = if in_buf {
+            scatter(&buf, arr, index)
+        } else {
+            scatter(arr, &mut buf, index)
+        };
+        in_buf ^= moved;
+    }
+
+    if in_buf {
+        arr.copy_from_slice(&buf);
+    }
+}
+
+/// Раскладывает `src` в `dst` по байту `index`. Возвращает `false`, если у всех
+/// элементов этот байт одинаков и перестановка не нужна.
+fn scatter<T: RadixKey>(src: &[T], dst: &mut [T], index: usize) -> bool {
+    let mut offsets = [0usize; 256];
+    for value in src {
+        offsets[value.key_byte(index) as usize] += 1;
+    }
+    if offsets.contains(&src.len()) {
+        return false;
+    }
+
+    let mut sum = 0;
+    for offset in &mut offsets {
+        let count = *offset;
+        *offset = sum;
+        sum += count;
+    }
+    for &value in src {
+        let bucket = &mut offsets[value.key_byte(index) as usize];
+        dst[*bucket] = value;
+        *bucket += 1;
+    }
+    true
+}
+
+/// Поразрядная сортировка MSD (MSD Radix Sort, American Flag Sort)
+/// Сортирует на месте, без дополнительного буфера. Нестабильна.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::radix_sort_msd;
+///
+/// let mut arr = [5u64, 2, 8, 1, 9];
+/// radix_sort_msd(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_sort::radix_sort_msd;
+///
+/// let mut arr = [300i64, -1, 0, i64::MIN, 7, i64::MAX, -300];
+/// radix_sort_msd(&mut arr);
+/// assert_eq!(arr, [i64::MIN, -300, -1, 0, 7, 300, i64::MAX]);
+/// ```
+pub fn radix_sort_msd<T: RadixKey>(arr: &mut [T]) {
+    american_flag(arr, 0, &|value: &T, depth| {
+        if depth < T::BYTES {
+            value.key_byte(T::BYTES - 1 - depth) as usize + 1
+        } else {
+            0
+        }
+    });
+}
+
+/// Поразрядная сортировка MSD для байтовых строк (`&[u8]`, `Vec<u8>`, `&str`, `String`).
+/// Строки упорядочиваются лексикографически по байтам, как `<[u8]>::cmp`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::radix_sort_bytes;
+///
+/// let mut arr = ["banana", "apple", "app", "", "cherry", "apple"];
+/// radix_sort_bytes(&mut arr);
+/// assert_eq!(arr, ["", "app", "apple", "apple", "banana", "cherry"]);
+/// ```
+///
+/// ```
+/// use exa_sort::radix_sort_bytes;
+///
+/// let mut arr = vec![vec![2u8, 0], vec![1], vec![2], vec![1, 255]];
+/// radix_sort_bytes(&mut arr);
+/// assert_eq!(arr, [vec![1], vec![1, 255], vec![2], vec![2, 0]]);
+/// ```
+///
+pub fn radix_sort_bytes<S: AsRef<[u8]>>(arr: &mut [S]) {
+    american_flag(arr, 0, &|value: &S, depth| {
+        value
+            .as_ref()
+            .get(depth)
+            .map_or(0, |&byte| byte as usize + 1)
+    });
+}
+
+/// American Flag Sort: раскладывает срез по корзине `key(value, depth)` на месте и
+/// рекурсивно сортирует корзины по следующему байту. Корзина 0 означает,
+/// что ключ закончился — такие элементы равны и уже на своём месте.
+fn american_flag<T, K>(arr: &mut [T], mut depth: usize, key: &K)
+where
+    K: Fn(&T, usize) -> usize,
+{
+    loop {
+        let len = arr.len();
+        if len <= MSD_INSERTION_THRESHOLD {
+            insertion_sort_by(arr, |a, b| compare_from(a, b, depth, key));
+            return;
+        }
+
+        let mut counts = [0usize; BUCKETS];
+        for value in arr.iter() {
+            counts[key(value, depth)] += 1;
+        }
+
+        // Все элементы в одной корзине: переходим к следующему байту без рекурсии.
+        if let Some(bucket) = counts.iter().position(|&count| count == len) {
+            if bucket == 0 {
+                return;
+            }
+            depth += 1;
+            continue;
+        }
+
+        let mut ends = [0usize; BUCKETS];
+        let mut sum = 0;
+        for (end, &count) in ends.iter_mut().zip(&counts) {
+            sum += count;
+            *end = sum;
+        }
+
+        // Перестановка на месте: каждый элемент отправляется в свою корзину.
+        let mut next: [usize; BUCKETS] = std::array::from_fn(|b| ends[b] - counts[b]);
+        for bucket in 0..BUCKETS {
+            while next[bucket] < ends[bucket] {
+                let target = key(&arr[next[bucket]], depth);
+                if target == bucket {
+                    next[bucket] += 1;
+                } else {
+                    arr.swap(next[bucket], next[target]);
+                    next[target] += 1;
+                }
+            }
+        }
+
+        for bucket in 1..BUCKETS {
+            let start = ends[bucket] - counts[bucket];
+            if counts[bucket] > 1 {
+                american_flag(&mut arr[start..ends[bucket]], depth + 1, key);
+            }
+        }
+        return;
+    }
+}
+
+/// Сравнение ключей начиная с байта `depth`.
+fn compare_from<T, K>(a: &T, b: &T, mut depth: usize, key: &K) -> Ordering
+where
+    K: Fn(&T, usize) -> usize,
+{
+    loop {
+        let (x, y) = (key(a, depth), key(b, depth));
+        if x != y || x == 0 {
+            return x.cmp(&y);
+        }
+        depth += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    /// Псевдослучайные 128-битные значения.
+    fn values(len: usize) -> impl Iterator<Item = u128> {
+        let mut rng = StdRng::seed_from_u64(0x2545_f491_4f6c_dd1d);
+        (0..len).map(move |_| rng.random())
+    }
+
+    macro_rules! check_types {
+        ($($t:ty),*) => {$(
+            let mut expected: Vec<$t> = values(5000).map(|v| v as $t).collect();
+            // Немного повторов и крайних значений.
+            expected.extend([<$t>::MIN, <$t>::MAX, 0 as $t, 0 as $t, 1 as $t]);
+            let source = expected.clone();
+            expected.sort();
+
+            let mut arr = source.clone();
+            radix_sort_lsd(&mut arr);
+            assert_eq!(arr, expected, "lsd {}", stringify!($t));
+
+            let mut arr = source.clone();
+            radix_sort_msd(&mut arr);
+            assert_eq!(arr, expected, "msd {}", stringify!($t));
+        )*};
+    }
+
+    #[test]
+    fn test_integers() {
+        check_types!(
+            u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+        );
+    }
+
+    #[test]
+    fn test_small_range() {
+        // Один меняющийся байт: LSD пропускает остальные проходы.
+        let mut arr: Vec<u64> = values(10_000).map(|v| (v % 100) as u64 * 256).collect();
+        let mut expected = arr.clone();
+        expected.sort();
+        radix_sort_lsd(&mut arr);
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_byte_strings() {
+        let mut arr: Vec<String> = values(5000)
+            .map(|v| format!("{:x}", v % 100_000).repeat((v % 3) as usize))
+            .collect();
+        arr.extend(["".into(), "a".repeat(10_000), "a".repeat(10_001)]);
+        let mut expected = arr.clone();
+        expected.sort();
+        radix_sort_bytes(&mut arr);
+        assert_eq!(arr, expected);
+
+        let mut arr: Vec<Vec<u8>> = values(5000)
+            .map(|v| v.to_le_bytes()[..(v % 17) as usize].to_vec())
+            .collect();
+        let mut expected = arr.clone();
+        expected.sort();
+        radix_sort_bytes(&mut arr);
+        assert_eq!(arr, expected);
+    }
+}
@@ -0,0 +1,407 @@
+//! Частичная сортировка и выбор k-го элемента.
+//!
+//!  * [`nth_element`] — быстрый выбор (quickselect): после разбиения продолжается только
+//!    та часть, в которой лежит искомый индекс. Опорный элемент выбирается как в
+//!    [`crate::intro_sort`], разбиение Хоара делит поровну и срезы с повторами. После
+//!    нескольких плохих разбиений опорный элемент выбирается медианой медиан, что
+//!    гарантирует O(n) в худшем случае;
+//!  * [`partial_sort`] — k наименьших элементов в начале среза по порядку, O(n log k);
+//!  * [`top_k`] — k наименьших элементов потока в куче ограниченного размера, O(k) памяти.
+
+use std::{cmp::Ordering, mem};
+
+use crate::{
+    heap_sort_by, heapify,
+    intro_sort::{NETWORK_THRESHOLD, choose_pivot, hoare_partition},
+    small::sort_network,
+};
+
+/// Сколько плохих разбиений допускается до перехода на медиану медиан.
+const BAD_PARTITIONS: u32 = 4;
+
+/// Выбор k-го по порядку элемента (quickselect)
+/// Переставляет элементы так, что на месте `k` оказывается элемент, который стоял бы там
+/// после сортировки; слева от него элементы не больше, справа — не меньше.
+/// Возвращает ссылку на этот элемент. Сложность O(n) в худшем случае.
+///
+/// # Паника
+///
+/// Если `k >= arr.len()`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::nth_element;
+///
+/// let mut arr = [2, 3, 1];
+/// assert_eq!(*nth_element(&mut arr, 1), 2);
+/// ```
+///
+/// ```
+/// use exa_sort::nth_element;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// assert_eq!(*nth_element(&mut arr, 0), 1);
+/// assert_eq!(*nth_element(&mut arr, 4), 9);
+/// ```
+///
+/// ```
+/// use exa_sort::nth_element;
+///
+/// let mut arr = [10, 4, 7, 3, 1, 8, 2, 9, 5, 6, 15, 12, 18, 11, 14, 17, 13, 16, 19, 1];
+/// let median = *nth_element(&mut arr, 10);
+/// assert_eq!(median, 10);
+/// assert!(arr[..10].iter().all(|&x| x <= median));
+/// assert!(arr[11..].iter().all(|&x| x >= median));
+/// ```
+pub fn nth_element<T: Ord>(arr: &mut [T], k: usize) -> &mut T {
+    nth_element_by(arr, k, T::cmp)
+}
+
+/// Выбор k-го элемента с пользовательской функцией сравнения.
+/// Аналог [`nth_element`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::nth_element_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// assert_eq!(*nth_element_by(&mut arr, 1, |a, b| b.cmp(a)), 8);
+/// ```
+pub fn nth_element_by<T, F>(arr: &mut [T], k: usize, mut compare: F) -> &mut T
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let len = arr.len();
+    assert!(k < len, "индекс {k} вне среза длиной {len}");
+    select(arr, k, 0, &mut compare);
+    &mut arr[k]
+}
+
+/// Выбор k-го элемента по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::nth_element_by_key;
+///
+/// let mut arr = [-5i32, 2, -8, 1, 9];
+/// assert_eq!(*nth_element_by_key(&mut arr, 2, |a| a.abs()), -5);
+/// ```
+pub fn nth_element_by_key<T, K, F>(arr: &mut [T], k: usize, mut key: F) -> &mut T
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    nth_element_by(arr, k, |a, b| key(a).cmp(&key(b)))
+}
+
+/// Быстрый выбор. `bad` — количество уже случившихся плохих разбиений: после
+/// [`BAD_PARTITIONS`] опорный элемент выбирается медианой медиан.
+fn select<T, F>(mut arr: &mut [T], mut k: usize, mut bad: u32, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    loop {
+        let len = arr.len();
+        if len <= NETWORK_THRESHOLD {
+            sort_network(arr, compare, &mut ());
+            return;
+        }
+
+        let pivot = if bad < BAD_PARTITIONS {
+            choose_pivot(arr, compare)
+        } else {
+            median_of_medians(arr, compare)
+        };
+        arr.swap(0, pivot);
+        let mid = hoare_partition(arr, compare, &mut ());
+
+        let rest = mem::take(&mut arr);
+        arr = match k.cmp(&mid) {
+            Ordering::Equal => return,
+            Ordering::Less => &mut rest[..mid],
+            Ordering::Greater => {
+                k -= mid + 1;
+                &mut rest[mid + 1..]
+            }
+        };
+        // Разбиение плохое, если отброшено меньше восьмой части.
+        if arr.len() > len / 8 * 7 {
+            bad += 1;
+        }
+    }
+}
+
+/// Медиана медиан групп по пять элементов. Медианы групп собираются в начале среза,
+/// их медиана находится рекурсивным выбором. Возвращает индекс опорного элемента,
+/// не меньшего и не большего чем примерно 3/10 элементов среза.
+fn median_of_medians<T, F>(arr: &mut [T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let groups = arr.len() / 5;
+    for group in 0..groups {
+        let start = group * 5;
+        sort_network(&mut arr[start..start + 5], compare, &mut ());
+        arr.swap(group, start + 2);
+    }
+    // Рекурсивный выбор сразу на медиане медиан, чтобы сохранить линейную сложность.
+    select(&mut arr[..groups], groups / 2, BAD_PARTITIONS, compare);
+    groups / 2
+}
+
+/// Частичная сортировка (Partial Sort)
+/// Переставляет в начало среза `k` наименьших элементов по возрастанию, порядок
+/// остальных не определён. Если `k` больше длины среза, сортируется весь срез.
+/// Использует кучу из `k` элементов, сложность O(n log k). Сортировка нестабильна.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::partial_sort;
+///
+/// let mut arr = [2, 3, 1];
+/// partial_sort(&mut arr, 2);
+/// assert_eq!(arr[..2], [1, 2]);
+/// ```
+///
+/// ```
+/// use exa_sort::partial_sort;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// partial_sort(&mut arr, 3);
+/// assert_eq!(arr[..3], [1, 2, 5]);
+/// ```
+///
+/// ```
+/// use exa_sort::partial_sort;
+///
+/// let mut arr = [10, 4, 7, 3, 1, 8, 2, 9, 5, 6, 15, 12, 18, 11, 14, 17, 13, 16, 19, 1];
+/// partial_sort(&mut arr, 5);
+/// assert_eq!(arr[..5], [1, 1, 2, 3, 4]);
+/// ```
+pub fn partial_sort<T: Ord>(arr: &mut [T], k: usize) {
+    partial_sort_by(arr, k, T::cmp);
+}
+
+/// Частичная сортировка с пользовательской функцией сравнения.
+/// Аналог [`partial_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::partial_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// partial_sort_by(&mut arr, 2, |a, b| b.cmp(a));
+/// assert_eq!(arr[..2], [9, 8]);
+/// ```
+pub fn partial_sort_by<T, F>(arr: &mut [T], k: usize, mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let k = k.min(arr.len());
+    if k == 0 {
+        return;
+    }
+
+    // Максимальная куча из первых k элементов: в корне наибольший из k наименьших.
+    for i in (0..k / 2).rev() {
+        heapify(arr, k, i, &mut compare, &mut ());
+    }
+    for i in k..arr.len() {
+        if compare(&arr[i], &arr[0]) == Ordering::Less {
+            arr.swap(0, i);
+            heapify(arr, k, 0, &mut compare, &mut ());
+        }
+    }
+    for i in (1..k).rev() {
+        arr.swap(0, i);
+        heapify(arr, i, 0, &mut compare, &mut ());
+    }
+}
+
+/// Частичная сортировка по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::partial_sort_by_key;
+///
+/// let mut arr = ["ccc", "a", "dddd", "bb"];
+/// partial_sort_by_key(&mut arr, 2, |a| a.len());
+/// assert_eq!(arr[..2], ["a", "bb"]);
+/// ```
+pub fn partial_sort_by_key<T, K, F>(arr: &mut [T], k: usize, mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    partial_sort_by(arr, k, |a, b| key(a).cmp(&key(b)));
+}
+
+/// `k` наименьших элементов потока по возрастанию.
+/// В памяти хранится не больше `k` элементов, сложность O(n log k).
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::top_k;
+///
+/// assert_eq!(top_k([2, 3, 1], 2), [1, 2]);
+/// assert_eq!(top_k([5, 2, 8, 1, 9], 10), [1, 2, 5, 8, 9]);
+/// assert_eq!(top_k((0..1_000_000).rev(), 3), [0, 1, 2]);
+/// ```
+pub fn top_k<T, I>(iter: I, k: usize) -> Vec<T>
+where
+    T: Ord,
+    I: IntoIterator<Item = T>,
+{
+    top_k_by(iter, k, T::cmp)
+}
+
+/// `k` первых по порядку `compare` элементов потока.
+/// Аналог [`top_k`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::top_k_by;
+///
+/// // Три наибольших.
+/// assert_eq!(top_k_by([5, 2, 8, 1, 9], 3, |a, b| b.cmp(a)), [9, 8, 5]);
+/// ```
+pub fn top_k_by<T, I, F>(iter: I, k: usize, mut compare: F) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let iter = iter.into_iter();
+    let mut heap = Vec::with_capacity(k.min(iter.size_hint().0));
+    if k == 0 {
+        return heap;
+    }
+
+    for item in iter {
+        if heap.len() < k {
+            heap.push(item);
+            if heap.len() == k {
+                for i in (0..k / 2).rev() {
+                    heapify(&mut heap, k, i, &mut compare, &mut ());
+                }
+            }
+        } else if compare(&item, &heap[0]) == Ordering::Less {
+            heap[0] = item;
+            heapify(&mut heap, k, 0, &mut compare, &mut ());
+        }
+    }
+
+    heap_sort_by(&mut heap, compare);
+    heap
+}
+
+/// `k` наименьших по ключу элементов потока.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::top_k_by_key;
+///
+/// let words = ["ccc", "a", "dddd", "bb"];
+/// assert_eq!(top_k_by_key(words, 2, |a| a.len()), ["a", "bb"]);
+/// ```
+pub fn top_k_by_key<T, I, K, F>(iter: I, k: usize, mut key: F) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    top_k_by(iter, k, |a, b| key(a).cmp(&key(b)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn random(len: usize, modulo: u32) -> Vec<u32> {
+        let mut rng = StdRng::seed_from_u64(0xdead_beef);
+        (0..len).map(|_| rng.random_range(0..modulo)).collect()
+    }
+
+    fn inputs() -> Vec<Vec<u32>> {
+        let len = 10_000;
+        vec![
+            random(len, u32::MAX),
+            random(len, 10),
+            (0..len as u32).collect(),
+            (0..len as u32).rev().collect(),
+            vec![7; len],
+            (0..len as u32).map(|i| i % 2 * 1000 + i / 2).collect(),
+        ]
+    }
+
+    #[test]
+    fn test_nth_element() {
+        for input in inputs() {
+            let mut sorted = input.clone();
+            sorted.sort();
+            for k in [0, 1, 17, 5000, 9999] {
+                let mut arr = input.clone();
+                let nth = *nth_element(&mut arr, k);
+                assert_eq!(nth, sorted[k]);
+                assert!(arr[..k].iter().all(|&x| x <= nth));
+                assert!(arr[k + 1..].iter().all(|&x| x >= nth));
+            }
+        }
+    }
+
+    #[test]
+    fn test_median_of_medians_linear() {
+        // Сразу медиана медиан: число сравнений растёт линейно.
+        for input in inputs() {
+            let comparisons = Cell::new(0);
+            let mut arr = input.clone();
+            let len = arr.len();
+            select(
+                &mut arr,
+                len / 2,
+                BAD_PARTITIONS,
+                &mut |a: &u32, b: &u32| {
+                    comparisons.set(comparisons.get() + 1);
+                    a.cmp(b)
+                },
+            );
+            let mut sorted = input;
+            sorted.sort();
+            assert_eq!(arr[len / 2], sorted[len / 2]);
+            assert!(comparisons.get() < 30 * len, "{}", comparisons.get());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "индекс 3 вне среза длиной 3")]
+    fn test_nth_element_out_of_bounds() {
+        nth_element(&mut [1, 2, 3], 3);
+    }
+
+    #[test]
+    fn test_partial_sort_and_top_k() {
+        for input in inputs() {
+            let mut sorted = input.clone();
+            sorted.sort();
+            for k in [0, 1, 100, 10_000, 20_000] {
+                let prefix = &sorted[..k.min(sorted.len())];
+                let mut arr = input.clone();
+                partial_sort(&mut arr, k);
+                assert_eq!(&arr[..prefix.len()], prefix);
+                assert_eq!(top_k(input.iter().copied(), k), prefix);
+            }
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Сортировка Шелла (Shell Sort).
+//!
+//! Улучшение сортировки вставками: сначала сортируются вставками элементы, отстоящие
+//! друг от друга на большое расстояние (шаг, gap), затем шаг уменьшается до 1.
+//! Дальние перестановки быстро переносят элементы близко к их месту, и последний проход
+//! с шагом 1 работает на почти отсортированном массиве. Сложность зависит от
+//! последовательности шагов ([`ShellGaps`]).
+
+use std::cmp::Ordering;
+
+use crate::instrument::{Collector, swap};
+
+/// Первые шаги последовательности Циуры, найденные экспериментально.
+const CIURA_GAPS: [usize; 9] = [1, 4, 10, 23, 57, 132, 301, 701, 1750];
+
+/// Последовательность шагов сортировки Шелла.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShellGaps {
+    /// Исходная последовательность Шелла `n/2, n/4, ..., 1`. Худший случай O(n²).
+    Shell,
+    /// Кнут: `(3^k - 1) / 2` = 1, 4, 13, 40, 121, ... Худший случай O(n^(3/2)).
+    Knuth,
+    /// Токуда: `⌈(9^k - 4^k) / (5 · 4^(k-1))⌉` = 1, 4, 9, 20, 46, 103, ...
+    Tokuda,
+    /// Циура: 1, 4, 10, 23, 57, 132, 301, 701, 1750, далее × 2.25.
+    /// Лучшая известная на практике.
+    #[default]
+    Ciura,
+}
+
+impl ShellGaps {
+    /// Шаги для массива длиной `len` в порядке убывания. Последний шаг всегда 1.
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// use exa_sort::ShellGaps;
+    ///
+    /// assert_eq!(ShellGaps::Shell.gaps(20), [10, 5, 2, 1]);
+    /// assert_eq!(ShellGaps::Knuth.gaps(50), [13, 4, 1]);
+    /// assert_eq!(ShellGaps::Tokuda.gaps(50), [46, 20, 9, 4, 1]);
+    /// assert_eq!(ShellGaps::Ciura.gaps(50), [23, 10, 4, 1]);
+    /// ```
+    pub fn gaps(self, len: usize) -> Vec<usize> {
+        let mut gaps = match self {
+            Self::Shell => {
+                let mut gaps = Vec::new();
+                let mut gap = len / 2;
+                while gap > 0 {
+                    gaps.push(gap);
+                    gap /= 2;
+                }
+                gaps.reverse();
+                gaps
+            }
+            Self::Knuth => {
+                // Кнут рекомендует шаги не больше трети длины массива.
+                successors(1, |gap| gap.checked_mul(3)?.checked_add(1), len / 3)
+            }
+            Self::Tokuda => {
+                let mut h = 1.0f64;
+                successors(
+                    1,
+                    |_| {
+                        h = 2.25f64.mul_add(h, 1.0);
+                        Some(h.ceil() as usize)
+                    },
+                    len,
+                )
+            }
+            Self::Ciura => {
+                let mut gaps: Vec<usize> = CIURA_GAPS
+                    .iter()
+                    .copied()
+                    .take_while(|&gap| gap < len)
+                    .collect();
+                if gaps.len() == CIURA_GAPS.len() {
+                    gaps.extend(
+                        successors(CIURA_GAPS[8], |gap| Some((gap as f64 * 2.25) as usize), len)
+                            .into_iter()
+                            .skip(1),
+                    );
+                }
+                gaps
+            }
+        };
+        if gaps.is_empty() {
+            gaps.push(1);
+        }
+        gaps.reverse();
+        gaps
+    }
+}
+
+/// Возрастающая последовательность `first, next(first), ...` из значений меньше `limit`.
+/// Первое значение включается всегда.
+fn successors(
+    first: usize,
+    mut next: impl FnMut(usize) -> Option<usize>,
+    limit: usize,
+) -> Vec<usize> {
+    let mut gaps = vec![first];
+    while let Some(gap) = next(gaps[gaps.len() - 1]).filter(|&gap| gap < limit) {
+        gaps.push(gap);
+    }
+    gaps
+}
+
+/// Сортировка Шелла (Shell Sort) с последовательностью шагов Циуры.
+/// Сортирует на месте без дополнительной памяти. Нестабильна.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::shell_sort;
+///
+/// let mut arr = [2, 3, 1];
+/// shell_sort(&mut arr);
+/// assert_eq!(arr, [1, 2 ,3]);
+/// ```
+///
+/// ```
+/// use exa_sort::shell_sort;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// shell_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_sort::shell_sort;
+///
+/// let mut arr = [10, 4, 7, 3, 1, 8, 2, 9, 5, 6, 15, 12, 18, 11, 14, 17, 13, 16, 19, 1];
+/// shell_sort(&mut arr);
+/// assert_eq!(arr, [1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
+/// ```
+pub fn shell_sort<T: Ord>(arr: &mut [T]) {
+    shell_sort_by(arr, T::cmp);
+}
+
+/// Сортировка Шелла с пользовательской функцией сравнения.
+/// Аналог [`shell_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::shell_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// shell_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn shell_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    shell_sort_with_gaps(arr, ShellGaps::default(), compare);
+}
+
+/// Сортировка Шелла по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::shell_sort_by_key;
+///
+/// let mut arr = ["ccc", "a", "bb"];
+/// shell_sort_by_key(&mut arr, |a| a.len());
+/// assert_eq!(arr, ["a", "bb", "ccc"]);
+/// ```
+pub fn shell_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    shell_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Сортировка Шелла с выбранной последовательностью шагов.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::{ShellGaps, shell_sort_with_gaps};
+///
+/// for gaps in [ShellGaps::Shell, ShellGaps::Knuth, ShellGaps::Tokuda, ShellGaps::Ciura] {
+///     let mut arr = [5, 2, 8, 1, 9];
+///     shell_sort_with_gaps(&mut arr, gaps, i32::cmp);
+///     assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// }
+/// ```
+pub fn shell_sort_with_gaps<T, F>(arr: &mut [T], gaps: ShellGaps, compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    shell_sort_with(arr, gaps, compare, &mut ());
+}
+
+pub(crate) fn shell_sort_with<T, F, C>(
+    arr: &mut [T],
+    gaps: ShellGaps,
+    mut compare: F,
+    collector: &mut C,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    let len = arr.len();
+    if len <= 1 {
+        return;
+    }
+
+    for gap in gaps.gaps(len) {
+        // Сортировка вставками элементов, отстоящих на `gap`.
+        for current in gap..len {
+            let mut i = current;
+            while i >= gap && compare(&arr[i], &arr[i - gap]) == Ordering::Less {
+                swap(arr, i, i - gap, collector);
+                i -= gap;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_GAPS: [ShellGaps; 4] = [
+        ShellGaps::Shell,
+        ShellGaps::Knuth,
+        ShellGaps::Tokuda,
+        ShellGaps::Ciura,
+    ];
+
+    #[test]
+    fn test_gaps() {
+        for gaps in ALL_GAPS {
+            for len in [0, 1, 2, 5, 100, 10_000, 1_000_000] {
+                let sequence = gaps.gaps(len);
+                assert_eq!(sequence.last(), Some(&1), "{gaps:?}, len = {len}");
+                assert!(
+                    sequence.is_sorted_by(|a, b| a > b),
+                    "{gaps:?}: {sequence:?}"
+                );
+                assert!(sequence[0] < len.max(2), "{gaps:?}: {sequence:?}");
+            }
+        }
+        assert_eq!(
+            ShellGaps::Tokuda.gaps(2000),
+            [1182, 525, 233, 103, 46, 20, 9, 4, 1]
+        );
+        assert_eq!(ShellGaps::Ciura.gaps(5000)[..2], [3937, 1750]);
+    }
+
+    #[test]
+    fn test_sort() {
+        let values: Vec<u32> = (0..20_000u32)
+            .map(|i| i.wrapping_mul(2_654_435_761) % 1000)
+            .collect();
+        let mut expected = values.clone();
+        expected.sort();
+        for gaps in ALL_GAPS {
+            let mut arr = values.clone();
+            shell_sort_with_gaps(&mut arr, gaps, u32::cmp);
+            assert_eq!(arr, expected, "{gaps:?}");
+        }
+    }
+}
@@ -0,0 +1,344 @@
+//! Сортировка коротких срезов.
+//!
+//!  * [`sort_small`] — сортирующие сети для массивов до [`MAX_NETWORK`] элементов.
+//!    Последовательность сравнений фиксирована и не зависит от данных, а каждый
+//!    компаратор выполняется без ветвлений: результат сравнения выбирает, какой из
+//!    двух элементов куда записать. Для N ≤ 12 сети оптимальны по числу компараторов,
+//!    для 13–16 — наименьшие известные;
+//!  * [`insertion_kernel`] — сортировка вставками с бинарным поиском без ветвлений:
+//!    количество сравнений при поиске места зависит только от длины, а не от данных.
+//!    Сортировка стабильна.
+//!
+//! Оба ядра используются как база рекурсии гибридных сортировок: сети — в
+//! [`crate::intro_sort`] и [`crate::nth_element`], вставки — в [`crate::merge_sort`].
+
+use std::{cmp::Ordering, mem::ManuallyDrop, ptr};
+
+use crate::instrument::{Collector, rotate_right};
+
+/// Наибольшая длина массива, для которой есть сортирующая сеть.
+pub const MAX_NETWORK: usize = 16;
+
+/// Сортирующие сети: пары индексов `(a, b)`, `a < b`, для массивов длиной 0..=16.
+/// Для N ≥ 5 каждая строка — слой компараторов, не зависящих друг от друга.
+#[rustfmt::skip]
+static NETWORKS: [&[(u8, u8)]; MAX_NETWORK + 1] = [
+    &[],
+    &[],
+    &[(0, 1)],
+    &[(0, 2), (0, 1), (1, 2)],
+    &[(0, 2), (1, 3), (0, 1), (2, 3), (1, 2)],
+    &[
+        (0, 3), (1, 4),
+        (0, 2), (1, 3),
+        (0, 1), (2, 4),
+        (1, 2), (3, 4),
+        (2, 3),
+    ],
+    &[
+        (0, 5), (1, 3), (2, 4),
+        (1, 2), (3, 4),
+        (0, 3), (2, 5),
+        (0, 1), (2, 3), (4, 5),
+        (1, 2), (3, 4),
+    ],
+    &[
+        (0, 6), (2, 3), (4, 5),
+        (0, 2), (1, 4), (3, 6),
+        (0, 1), (2, 5), (3, 4),
+        (1, 2), (4, 6),
+        (2, 3), (4, 5),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    &[
+        (0, 2), (1, 3), (4, 6), (5, 7),
+        (0, 4), (1, 5), (2, 6), (3, 7),
+        (0, 1), (2, 3), (4, 5), (6, 7),
+        (2, 4), (3, 5),
+        (1, 4), (3, 6),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    &[
+        (0, 3), (1, 7), (2, 5), (4, 8),
+        (0, 7), (2, 4), (3, 8), (5, 6),
+        (0, 2), (1, 3), (4, 5), (7, 8),
+        (1, 4), (3, 6), (5, 7),
+        (0, 1), (2, 4), (3, 5), (6, 8),
+        (2, 3), (4, 5), (6, 7),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    &[
+        (0, 8), (1, 9), (2, 7), (3, 5), (4, 6),
+        (0, 2), (1, 4), (5, 8), (7, 9),
+        (0, 3), (2, 4), (5, 7), (6, 9),
+        (0, 1), (3, 6), (8, 9),
+        (1, 5), (2, 3), (4, 8), (6, 7),
+        (1, 2), (3, 5), (4, 6), (7, 8),
+        (2, 3), (4, 5), (6, 7),
+        (3, 4), (5, 6),
+    ],
+    &[
+        (0, 9), (1, 6), (2, 4), (3, 7), (5, 8),
+        (0, 1), (3, 5), (4, 10), (6, 9), (7, 8),
+        (1, 3), (2, 5), (4, 7), (8, 10),
+        (0, 4), (1, 2), (3, 7), (5, 9), (6, 8),
+        (0, 1), (2, 6), (4, 5), (7, 8), (9, 10),
+        (2, 4), (3, 6), (5, 7), (8, 9),
+        (1, 2), (3, 4), (5, 6), (7, 8),
+        (2, 3), (4, 5), (6, 7),
+    ],
+    &[
+        (0, 8), (1, 7), (2, 6), (3, 11), (4, 10), (5, 9),
+        (0, 1), (2, 5), (3, 4), (6, 9), (7, 8), (10, 11),
+        (0, 2), (1, 6), (5, 10), (9, 11),
+        (0, 3), (1, 2), (4, 6), (5, 7), (8, 11), (9, 10),
+        (1, 4), (3, 5), (6, 8), (7, 10),
+        (1, 3), (2, 5), (6, 9), (8, 10),
+        (2, 3), (4, 5), (6, 7), (8, 9),
+        (4, 6), (5, 7),
+        (3, 4), (5, 6), (7, 8),
+    ],
+    &[
+        (0, 12), (1, 10), (2, 9), (3, 7), (5, 11), (6, 8),
+        (1, 6), (2, 3), (4, 11), (7, 9), (8, 10),
+        (0, 4), (1, 2), (3, 6), (7, 8), (9, 10), (11, 12),
+        (4, 6), (5, 9), (8, 11), (10, 12),
+        (0, 5), (3, 8), (4, 7), (6, 11), (9, 10),
+        (0, 1), (2, 5), (6, 9), (7, 8), (10, 11),
+        (1, 3), (2, 4), (5, 6), (9, 10),
+        (1, 2), (3, 4), (5, 7), (6, 8),
+        (2, 3), (4, 5), (6, 7), (8, 9),
+        (3, 4), (5, 6),
+    ],
+    &[
+        (0, 13), (1, 12), (4, 8), (5, 6), (7, 11), (9, 10),
+        (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (11, 12),
+        (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13),
+        (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9),
+        (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11),
+        (1, 4), (2, 6), (5, 8), (7, 10), (9, 13),
+        (2, 4), (3, 6), (9, 12), (11, 13),
+        (3, 5), (6, 8), (7, 9), (10, 12),
+        (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+        (6, 7), (8, 9),
+    ],
+    &[
+        (0, 13), (1, 12), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+        (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (11, 12),
+        (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13),
+        (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14),
+        (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+        (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+        (2, 4), (3, 6), (9, 12), (11, 13),
+        (3, 5), (6, 8), (7, 9), (10, 12),
+        (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+        (6, 7), (8, 9),
+    ],
+    &[
+        (0, 13), (1, 12), (2, 15), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+        (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (10, 15), (11, 12),
+        (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13), (14, 15),
+        (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14), (13, 15),
+        (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+        (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+        (2, 4), (3, 6), (9, 12), (11, 13),
+        (3, 5), (6, 8), (7, 9), (10, 12),
+        (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+        (6, 7), (8, 9),
+    ],
+];
+
+/// Сортировка массива длиной до 16 сортирующей сетью
+/// Длина массива проверяется при компиляции. Сама сеть берётся из таблицы и
+/// выполняется циклом по её компараторам: код не разворачивается, но каждый
+/// компаратор работает без ветвлений по данным. Сортировка нестабильна.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::sort_small;
+///
+/// let mut arr = [2, 3, 1];
+/// sort_small(&mut arr);
+/// assert_eq!(arr, [1, 2 ,3]);
+/// ```
+///
+/// ```
+/// use exa_sort::sort_small;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// sort_small::<5, _>(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_sort::sort_small;
+///
+/// let mut arr = [10, 4, 7, 3, 1, 8, 2, 9, 5, 6, 15, 12, 18, 11, 14, 17];
+/// sort_small(&mut arr);
+/// assert_eq!(arr, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 14, 15, 17, 18]);
+/// ```
+///
+/// Массивы длиннее 16 не компилируются:
+///
+/// ```compile_fail
+/// let mut arr = [0; 17];
+/// exa_sort::sort_small(&mut arr);
+/// ```
+pub fn sort_small<const N: usize, T: Ord>(arr: &mut [T; N]) {
+    sort_small_by(arr, T::cmp);
+}
+
+/// Сортировка сетью с пользовательской функцией сравнения.
+/// Аналог [`sort_small`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::sort_small_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// sort_small_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn sort_small_by<const N: usize, T, F>(arr: &mut [T; N], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    const {
+        assert!(N <= MAX_NETWORK, "сортирующие сети есть только для N <= 16")
+    };
+    sort_network(arr, &mut compare, &mut ());
+}
+
+/// Сортировка сетью по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::sort_small_by_key;
+///
+/// let mut arr = [-5i32, 2, -8, 1, 9];
+/// sort_small_by_key(&mut arr, |a| a.abs());
+/// assert_eq!(arr, [1, 2, -5, -8, 9]);
+/// ```
+pub fn sort_small_by_key<const N: usize, T, K, F>(arr: &mut [T; N], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_small_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Сортирует срез длиной до [`MAX_NETWORK`] сортирующей сетью.
+#[inline]
+pub(crate) fn sort_network<T, F, C>(arr: &mut [T], compare: &mut F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    for &(a, b) in NETWORKS[arr.len()] {
+        compare_exchange(arr, a as usize, b as usize, compare, collector);
+    }
+}
+
+/// Компаратор сети: упорядочивает `arr[a]` и `arr[b]`, `a < b`.
+/// Оба элемента перезаписываются в любом случае, результат сравнения только
+/// выбирает источники, поэтому ветвления по данным нет.
+#[inline(always)]
+fn compare_exchange<T, F, C>(arr: &mut [T], a: usize, b: usize, compare: &mut F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    assert!(a < b && b < arr.len());
+    let base = arr.as_mut_ptr();
+    // SAFETY: `a < b < arr.len()`, поэтому указатели различны и указывают внутрь среза.
+    // До сравнения ничего не перемещается, так что паника в `compare` безопасна.
+    // Дальше `lo` читается во временную копию, `hi` переносится в `b`, копия — в `a`:
+    // каждый элемент остаётся ровно в одной ячейке.
+    let less = unsafe {
+        let (pa, pb) = (base.add(a), base.add(b));
+        let less = compare(&*pb, &*pa) == Ordering::Less;
+        let (lo, hi) = if less { (pb, pa) } else { (pa, pb) };
+        let tmp = ManuallyDrop::new(ptr::read(lo));
+        ptr::copy(hi, pb, 1);
+        ptr::copy_nonoverlapping(&*tmp, pa, 1);
+        less
+    };
+    if less {
+        collector.swap(arr, a, b);
+    }
+}
+
+/// Стабильная сортировка вставками с бинарным поиском без ветвлений.
+/// Место для `arr[i]` ищется за `⌈log2 i⌉ + 1` сравнений независимо от данных,
+/// затем элемент вставляется циклическим сдвигом.
+pub(crate) fn insertion_kernel<T, F, C>(arr: &mut [T], compare: &mut F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    for i in 1..arr.len() {
+        // Первая позиция в `arr[..i]` с элементом больше `arr[i]`.
+        let mut base = 0;
+        let mut size = i;
+        while size > 1 {
+            let half = size / 2;
+            let mid = base + half;
+            let greater = compare(&arr[mid], &arr[i]) == Ordering::Greater;
+            base = if greater { base } else { mid };
+            size -= half;
+        }
+        let pos = base + usize::from(compare(&arr[base], &arr[i]) != Ordering::Greater);
+        if pos < i {
+            rotate_right(&mut arr[pos..=i], collector);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn test_network_sizes() {
+        let sizes: Vec<usize> = NETWORKS.iter().map(|network| network.len()).collect();
+        let best = [0, 0, 1, 3, 5, 9, 12, 16, 19, 25, 29, 35, 39, 45, 51, 56, 60];
+        assert_eq!(sizes, best);
+    }
+
+    #[test]
+    fn test_zero_one_principle() {
+        // Сеть сортирует любые входы, если сортирует все последовательности из 0 и 1.
+        for len in 0..=MAX_NETWORK {
+            for mask in 0..1u32 << len {
+                let mut arr: Vec<u32> = (0..len).map(|i| mask >> i & 1).collect();
+                sort_network(&mut arr, &mut u32::cmp, &mut ());
+                assert!(arr.is_sorted(), "len = {len}, mask = {mask:b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_owned() {
+        let mut arr = ["груша", "яблоко", "абрикос", "вишня", "абрикос"].map(String::from);
+        sort_small(&mut arr);
+        assert_eq!(arr, ["абрикос", "абрикос", "вишня", "груша", "яблоко"]);
+    }
+
+    #[test]
+    fn test_insertion_kernel_stable() {
+        let mut rng = StdRng::seed_from_u64(0x0bad_cafe);
+        for len in 0..=40 {
+            let mut arr: Vec<(u32, usize)> =
+                (0..len).map(|i| (rng.random_range(0..5), i)).collect();
+            let mut expected = arr.clone();
+            expected.sort_by_key(|&(key, _)| key);
+            insertion_kernel(&mut arr, &mut |a, b| a.0.cmp(&b.0), &mut ());
+            assert_eq!(arr, expected);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Быстрая сортировка с трёхсторонним разбиением (задача о голландском флаге Дейкстры).
+//!
+//! Обычное разбиение Ломуто в [`crate::quick_sort`] отправляет все равные опорному элементы
+//! в одну часть, поэтому на массивах с большим количеством повторов глубина рекурсии растёт
+//! до O(n), а сложность — до O(n²). Трёхстороннее разбиение делит срез на части
+//! `< pivot`, `== pivot` и `> pivot`; средняя часть уже на своём месте и в рекурсию не попадает.
+//! На массиве из `k` различных значений сложность O(n log k).
+
+use std::{cmp::Ordering, mem};
+
+use crate::{
+    instrument::{Collector, swap},
+    intro_sort::choose_pivot,
+};
+
+/// Быстрая сортировка с трёхсторонним разбиением (3-way Quick Sort)
+/// Эффективна на данных с большим количеством повторяющихся элементов.
+/// Сортировка нестабильна.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::three_way_quick_sort;
+///
+/// let mut arr = [2, 3, 1];
+/// three_way_quick_sort(&mut arr);
+/// assert_eq!(arr, [1, 2 ,3]);
+/// ```
+///
+/// ```
+/// use exa_sort::three_way_quick_sort;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// three_way_quick_sort(&mut arr);
+/// assert_eq!(arr, [1, 2, 5, 8, 9]);
+/// ```
+///
+/// ```
+/// use exa_sort::three_way_quick_sort;
+///
+/// let mut arr = [1, 3, 1, 2, 3, 1, 2, 2, 3, 1, 1, 3];
+/// three_way_quick_sort(&mut arr);
+/// assert_eq!(arr, [1, 1, 1, 1, 1, 2, 2, 2, 3, 3, 3, 3]);
+/// ```
+pub fn three_way_quick_sort<T: Ord>(arr: &mut [T]) {
+    three_way_quick_sort_by(arr, T::cmp);
+}
+
+/// Быстрая сортировка с трёхсторонним разбиением и пользовательской функцией сравнения.
+/// Аналог [`three_way_quick_sort`], порядок элементов задаёт `compare`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::three_way_quick_sort_by;
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// three_way_quick_sort_by(&mut arr, |a, b| b.cmp(a));
+/// assert_eq!(arr, [9, 8, 5, 2, 1]);
+/// ```
+pub fn three_way_quick_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    three_way_quick_sort_with(arr, compare, &mut ());
+}
+
+/// Быстрая сортировка с трёхсторонним разбиением по ключу, который вычисляет `key`.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::three_way_quick_sort_by_key;
+///
+/// let mut arr = ["ccc", "a", "bb", "b"];
+/// three_way_quick_sort_by_key(&mut arr, |a| a.len());
+/// assert_eq!(arr[..2].iter().map(|a| a.len()).collect::<Vec<_>>(), [1, 1]);
+/// assert_eq!(arr[2..], ["bb", "ccc"]);
+/// ```
+pub fn three_way_quick_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    three_way_quick_sort_by(arr, |a, b| key(a).cmp(&key(b)));
+}
+
+pub(crate) fn three_way_quick_sort_with<T, F, C>(arr: &mut [T], mut compare: F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    three_way_helper(arr, &mut compare, collector);
+}
+
+fn three_way_helper<T, F, C>(mut arr: &mut [T], compare: &mut F, collector: &mut C)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    while arr.len() > 1 {
+        let pivot = choose_pivot(arr, compare);
+        swap(arr, 0, pivot, collector);
+        let (lt, gt) = three_way_partition(arr, compare, collector);
+
+        // Рекурсивно сортируем меньшую часть, большую обрабатываем в цикле.
+        let (left, rest) = mem::take(&mut arr).split_at_mut(lt);
+        let right = &mut rest[gt - lt..];
+        if left.len() < right.len() {
+            three_way_helper(left, compare, collector);
+            arr = right;
+        } else {
+            three_way_helper(right, compare, collector);
+            arr = left;
+        }
+    }
+}
+
+/// Трёхстороннее разбиение относительно опорного элемента `arr[0]`.
+/// Возвращает `(lt, gt)`: `arr[..lt] < pivot`, `arr[lt..gt] == pivot`, `arr[gt..] > pivot`.
+fn three_way_partition<T, F, C>(arr: &mut [T], compare: &mut F, collector: &mut C) -> (usize, usize)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    C: Collector<T>,
+{
+    // `arr[lt]` всегда равен опорному элементу: это начало области равных.
+    let mut lt = 0;
+    let mut i = 1;
+    let mut gt = arr.len();
+
+    while i < gt {
+        match compare(&arr[i], &arr[lt]) {
+            Ordering::Less => {
+                swap(arr, lt, i, collector);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                swap(arr, i, gt, collector);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+
+    (lt, gt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition() {
+        let mut arr = [2, 3, 1, 2, 3, 1, 2];
+        let (lt, gt) = three_way_partition(&mut arr, &mut i32::cmp, &mut ());
+        assert_eq!((lt, gt), (2, 5));
+        assert!(arr[..lt].iter().all(|&v| v == 1));
+        assert!(arr[lt..gt].iter().all(|&v| v == 2));
+        assert!(arr[gt..].iter().all(|&v| v == 3));
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let mut arr: Vec<u32> = (0..100_000).map(|i| i % 100).collect();
+        let mut expected = arr.clone();
+        expected.sort();
+        three_way_quick_sort(&mut arr);
+        assert_eq!(arr, expected);
+    }
+}
@@ -0,0 +1,500 @@
+//! Запись хода сортировки для пошагового воспроизведения.
+//!
+//! [`Trace::record`] запускает сортировку ([`Algorithm`]) и записывает каждое сравнение, обмен
+//! и перемещение элемента как событие над индексами среза ([`TraceEvent`]). Элементы,
+//! вынесенные в буфер слияния, обозначаются ячейками [`Location::Buffer`], элемент во
+//! временной переменной — [`Location::Temp`].
+//!
+//! Записанный ход можно воспроизвести ([`Trace::replay`]), нарисовать столбчатыми
+//! диаграммами ([`Trace::render`]) или выгрузить в JSON ([`Trace::to_json`]).
+
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    fmt::{self, Display, Write as _},
+};
+
+use crate::instrument::{Algorithm, SortStats, Tracer};
+
+/// Положение элемента во время сортировки.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Location {
+    /// Индекс в сортируемом срезе.
+    Slice(usize),
+    /// Ячейка вспомогательной памяти.
+    Buffer(usize),
+    /// Временная переменная.
+    Temp,
+}
+
+/// Событие сортировки.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceEvent {
+    /// Сравнение двух элементов.
+    Compare(Location, Location),
+    /// Обмен двух элементов.
+    Swap(Location, Location),
+    /// Перемещение элемента из `src` в `dst`.
+    Write {
+        /// Куда.
+        dst: Location,
+        /// Откуда.
+        src: Location,
+    },
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::Slice(index) => write!(f, "[{index}]"),
+            Location::Buffer(index) => write!(f, "буфер[{index}]"),
+            Location::Temp => write!(f, "tmp"),
+        }
+    }
+}
+
+impl Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceEvent::Compare(a, b) => write!(f, "сравнение {a} и {b}"),
+            TraceEvent::Swap(a, b) => write!(f, "обмен {a} и {b}"),
+            TraceEvent::Write { dst, src } => write!(f, "запись {dst} ← {src}"),
+        }
+    }
+}
+
+impl TraceEvent {
+    /// Положения, которых касается событие.
+    fn locations(&self) -> [Location; 2] {
+        match *self {
+            TraceEvent::Compare(a, b) | TraceEvent::Swap(a, b) => [a, b],
+            TraceEvent::Write { dst, src } => [dst, src],
+        }
+    }
+}
+
+/// Записанный ход сортировки.
+///
+/// # Примеры
+///
+/// ```
+/// use exa_sort::{Algorithm, Trace, TraceEvent, Location};
+///
+/// let mut arr = [2, 3, 1];
+/// let trace = Trace::record(&mut arr, Algorithm::Insertion);
+/// assert_eq!(arr, [1, 2, 3]);
+///
+/// assert_eq!(trace.initial(), [2, 3, 1]);
+/// assert_eq!(trace.stats().comparisons, trace.events().iter().filter(|event| matches!(event, TraceEvent::Compare(..))).count());
+/// assert!(trace.events().contains(&TraceEvent::Swap(Location::Slice(2), Location::Slice(1))));
+///
+/// let mut last = Vec::new();
+/// trace.replay(|_, state| last = state.to_vec());
+/// assert_eq!(last, [1, 2, 3]);
+/// ```
+///
+/// ```
+/// use exa_sort::{Algorithm, Trace};
+///
+/// let mut arr = [5, 2, 8, 1, 9];
+/// let trace = Trace::record(&mut arr, Algorithm::Selection);
+///
+/// let text = trace.render(3);
+/// assert!(text.starts_with("Шаг 0: начало\n"));
+/// assert!(text.contains("обмен [0] и [3]"));
+///
+/// let json = trace.to_json();
+/// assert!(json.starts_with(r#"{"initial":[5,2,8,1,9],"events":[{"type":"compare""#));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Trace<T> {
+    initial: Vec<T>,
+    events: Vec<TraceEvent>,
+    stats: SortStats,
+}
+
+impl<T: Clone> Trace<T> {
+    /// Сортирует `arr` алгоритмом `algorithm` по возрастанию и записывает ход сортировки.
+    pub fn record(arr: &mut [T], algorithm: Algorithm) -> Self
+    where
+        T: Ord,
+    {
+        Self::record_by(arr, T::cmp, algorithm)
+    }
+
+    /// Как [`Trace::record`], но элементы сравнивает `compare`.
+    pub fn record_by<F>(arr: &mut [T], mut compare: F, algorithm: Algorithm) -> Self
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let initial = arr.to_vec();
+        let tracer = RefCell::new(Tracer::new(arr));
+        let traced = |a: &T, b: &T| {
+            tracer.borrow_mut().compare(a, b);
+            compare(a, b)
+        };
+        algorithm.sort_with(arr, traced, &mut &tracer);
+        let tracer = tracer.into_inner();
+        Self {
+            initial,
+            events: tracer.events,
+            stats: tracer.stats,
+        }
+    }
+
+    /// Исходное содержимое среза.
+    pub fn initial(&self) -> &[T] {
+        &self.initial
+    }
+
+    /// Записанные события.
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    /// Статистика той же сортировки.
+    pub fn stats(&self) -> SortStats {
+        self.stats
+    }
+
+    /// Воспроизводит сортировку: после каждого события вызывает `step` с событием
+    /// и состоянием среза.
+    pub fn replay<F>(&self, mut step: F)
+    where
+        F: FnMut(&TraceEvent, &[T]),
+    {
+        let mut state = State {
+            slice: self.initial.clone(),
+            buffer: Vec::new(),
+            temp: None,
+        };
+        for event in &self.events {
+            match *event {
+                TraceEvent::Compare(..) => {}
+                TraceEvent::Swap(Location::Slice(a), Location::Slice(b)) => state.slice.swap(a, b),
+                TraceEvent::Swap(a, b) => {
+                    let (value_a, value_b) = (state.get(a), state.get(b));
+                    state.set(a, value_b);
+                    state.set(b, value_a);
+                }
+                TraceEvent::Write { dst, src } => {
+                    let value = state.get(src);
+                    state.set(dst, value);
+                }
+            }
+            step(event, &state.slice);
+        }
+    }
+
+    /// Рисует каждый шаг столбчатой диаграммой высотой `height` строк. Высота столбца
+    /// пропорциональна рангу элемента, под диаграммой `^` отмечены затронутые индексы.
+    pub fn render(&self, height: usize) -> String
+    where
+        T: Ord,
+    {
+        let mut ranks = self.initial.clone();
+        ranks.sort();
+        ranks.dedup();
+
+        let mut out = String::new();
+        let mut draw = |title: &dyn Display, state: &[T], marked: &[Location]| {
+            let _ = writeln!(out, "{title}");
+            let bars: Vec<usize> = state
+                .iter()
+                .map(|value| {
+                    let rank = ranks.partition_point(|x| x < value) + 1;
+                    (rank * height).div_ceil(ranks.len())
+                })
+                .collect();
+            for row in (1..=height).rev() {
+                let line: String = bars
+                    .iter()
+                    .map(|&bar| if bar >= row { '█' } else { ' ' })
+                    .collect();
+                let _ = writeln!(out, "{}", line.trim_end());
+            }
+            let marks: String = (0..state.len())
+                .map(|i| {
+                    if marked.contains(&Location::Slice(i)) {
+                        '^'
+                    } else {
+                        ' '
+                    }
+                })
+                .collect();
+            let _ = writeln!(out, "{}", marks.trim_end());
+        };
+
+        draw(&"Шаг 0: начало", &self.initial, &[]);
+        let mut number = 0;
+        self.replay(|event, state| {
+            number += 1;
+            draw(
+                &format_args!("Шаг {number}: {event}"),
+                state,
+                &event.locations(),
+            );
+        });
+        out
+    }
+
+    /// Выгружает ход сортировки в JSON:
+    /// `{"initial":[...],"events":[{"type":"swap","a":{"slice":0},"b":{"slice":3}}, ...]}`.
+    ///
+    /// Значения, текст которых ([`Display`]) — число в синтаксисе JSON, записываются
+    /// числами, остальные — строками: `01`, `1.` или `NaN` остаются строками.
+    /// Положения: `{"slice":i}`, `{"buffer":i}` или `"temp"`.
+    pub fn to_json(&self) -> String
+    where
+        T: Display,
+    {
+        let mut out = String::from(r#"{"initial":["#);
+        for (i, value) in self.initial.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            json_value(&mut out, &value.to_string());
+        }
+        out.push_str(r#"],"events":["#);
+        for (i, event) in self.events.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let (kind, first, second) = match *event {
+                TraceEvent::Compare(a, b) => ("compare", ("a", a), ("b", b)),
+                TraceEvent::Swap(a, b) => ("swap", ("a", a), ("b", b)),
+                TraceEvent::Write { dst, src } => ("write", ("dst", dst), ("src", src)),
+            };
+            let _ = write!(
+                out,
+                r#"{{"type":"{kind}","{}":{},"{}":{}}}"#,
+                first.0,
+                json_location(first.1),
+                second.0,
+                json_location(second.1)
+            );
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+/// Состояние при воспроизведении.
+struct State<T> {
+    slice: Vec<T>,
+    buffer: Vec<Option<T>>,
+    temp: Option<T>,
+}
+
+impl<T: Clone> State<T> {
+    fn get(&self, location: Location) -> T {
+        match location {
+            Location::Slice(index) => self.slice[index].clone(),
+            Location::Buffer(index) => self.buffer[index]
+                .clone()
+                .expect("чтение из пустой ячейки буфера"),
+            Location::Temp => self
+                .temp
+                .clone()
+                .expect("чтение пустой временной переменной"),
+        }
+    }
+
+    fn set(&mut self, location: Location, value: T) {
+        match location {
+            Location::Slice(index) => self.slice[index] = value,
+            Location::Buffer(index) => {
+                if index >= self.buffer.len() {
+                    self.buffer.resize(index + 1, None);
+                }
+                self.buffer[index] = Some(value);
+            }
+            Location::Temp => self.temp = Some(value),
+        }
+    }
+}
+
+fn json_location(location: Location) -> String {
+    match location {
+        Location::Slice(index) => format!(r#"{{"slice":{index}}}"#),
+        Location::Buffer(index) => format!(r#"{{"buffer":{index}}}"#),
+        Location::Temp => r#""temp""#.to_string(),
+    }
+}
+
+/// Соответствует ли `text` грамматике числа JSON: `-?(0|[1-9]\d*)(\.\d+)?([eE][+-]?\d+)?`.
+fn is_json_number(text: &str) -> bool {
+    fn digits(bytes: &[u8]) -> usize {
+        bytes.iter().take_while(|b| b.is_ascii_digit()).count()
+    }
+
+    let mut rest = text.strip_prefix('-').unwrap_or(text).as_bytes();
+    let integer = digits(rest);
+    if integer == 0 || (integer > 1 && rest[0] == b'0') {
+        return false;
+    }
+    rest = &rest[integer..];
+    if let Some(fraction) = rest.strip_prefix(b".") {
+        let count = digits(fraction);
+        if count == 0 {
+            return false;
+        }
+        rest = &fraction[count..];
+    }
+    if let Some(exponent) = rest.strip_prefix(b"e").or_else(|| rest.strip_prefix(b"E")) {
+        let exponent = exponent
+            .strip_prefix(b"+")
+            .or_else(|| exponent.strip_prefix(b"-"))
+            .unwrap_or(exponent);
+        let count = digits(exponent);
+        if count == 0 {
+            return false;
+        }
+        rest = &exponent[count..];
+    }
+    rest.is_empty()
+}
+
+fn json_value(out: &mut String, value: &str) {
+    if is_json_number(value) {
+        out.push_str(value);
+        return;
+    }
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str(r#"\""#),
+            '\\' => out.push_str(r"\\"),
+            '\n' => out.push_str(r"\n"),
+            c if c < ' ' => {
+                let _ = write!(out, r"\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn random(len: usize) -> Vec<u32> {
+        let mut rng = StdRng::seed_from_u64(0x2545_f491);
+        (0..len).map(|_| rng.random_range(0..1000)).collect()
+    }
+
+    #[test]
+    fn test_replay() {
+        for algorithm in Algorithm::ALL {
+            for len in [0, 1, 2, 10, 100, 300] {
+                let mut arr = random(len);
+                let trace = Trace::record(&mut arr, algorithm);
+                assert!(arr.is_sorted(), "{algorithm:?}, len = {len}");
+
+                let mut state = trace.initial().to_vec();
+                trace.replay(|_, slice| state = slice.to_vec());
+                assert_eq!(state, arr, "{algorithm:?}, len = {len}");
+
+                let compares = trace
+                    .events()
+                    .iter()
+                    .filter(|event| matches!(event, TraceEvent::Compare(..)))
+                    .count();
+                assert_eq!(
+                    compares,
+                    trace.stats().comparisons,
+                    "{algorithm:?}, len = {len}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let mut arr = [2, 3, 1];
+        let trace = Trace::record(&mut arr, Algorithm::Bubble);
+        let expected = "\
+Шаг 0: начало
+ █
+██
+███
+
+Шаг 1: сравнение [0] и [1]
+ █
+██
+███
+^^
+Шаг 2: сравнение [1] и [2]
+ █
+██
+███
+ ^^
+Шаг 3: обмен [1] и [2]
+  █
+█ █
+███
+ ^^
+";
+        assert!(trace.render(3).starts_with(expected));
+    }
+
+    #[test]
+    fn test_json_number() {
+        for number in [
+            "0", "-0", "7", "-12", "1.5", "0.25", "1e9", "2E-3", "-1.5e+10",
+        ] {
+            assert!(is_json_number(number), "{number}");
+        }
+        for text in [
+            "", "-", "01", "-01", "1.", ".5", "+1", "1e", "1e+", "NaN", "inf", "1 ",
+        ] {
+            assert!(!is_json_number(text), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_json_parse() {
+        let mut arr = ["01", "1.", "x", "1", "-0.5", "1e3", "\\", "\u{1}"];
+        let trace = Trace::record(&mut arr, Algorithm::Insertion);
+        let json: serde_json::Value = serde_json::from_str(&trace.to_json()).unwrap();
+
+        let initial = json["initial"].as_array().unwrap();
+        assert_eq!(initial[..3], ["01", "1.", "x"]);
+        assert_eq!(initial[3], 1);
+        assert_eq!(initial[4], -0.5);
+        assert_eq!(initial[5], 1000.0);
+        assert_eq!(initial[6], "\\");
+        assert_eq!(initial[7], "\u{1}");
+
+        let events = json["events"].as_array().unwrap();
+        assert_eq!(events.len(), trace.events().len());
+        assert_eq!(events[0]["type"], "compare");
+        assert_eq!(events[0]["a"]["slice"], 1);
+    }
+
+    #[test]
+    fn test_json() {
+        let mut arr = ["б\"", "а\n"];
+        let trace = Trace::record(&mut arr, Algorithm::Insertion);
+        assert_eq!(
+            trace.to_json(),
+            r#"{"initial":["б\"","а\n"],"events":[{"type":"compare","a":{"slice":1},"b":{"slice":0}},{"type":"swap","a":{"slice":1},"b":{"slice":0}}]}"#
+        );
+
+        let mut arr = [1.5, -2.0, f64::NAN];
+        let trace = Trace::record_by(&mut arr, f64::total_cmp, Algorithm::BottomUpMerge);
+        assert!(
+            trace
+                .to_json()
+                .starts_with(r#"{"initial":[1.5,-2,"NaN"],"events":[{"#)
+        );
+        assert!(
+            trace
+                .to_json()
+                .contains(r#"{"type":"write","dst":{"buffer":0},"src":{"slice":0}}"#)
+        );
+    }
+}
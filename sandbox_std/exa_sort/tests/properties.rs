@@ -0,0 +1,385 @@
+//! Общие проверки всех сортировок на случайных и враждебных входах.
+//!
+//! Каждая сортировка запускается на входах из [`generators`]: результат должен быть
+//! упорядочен, быть перестановкой входа, а для стабильных сортировок — совпадать со
+//! стабильной сортировкой стандартной библиотеки. Элементы — пары (ключ, позиция во входе),
+//! сравниваются только по ключу, поэтому нарушение стабильности видно по позициям.
+//!
+//! Входы случайные; зерно выводится в сообщении об ошибке и задаётся переменной окружения
+//! `EXA_SORT_SEED` для повторения.
+
+use std::{cmp::Ordering, env, sync::Mutex};
+
+use exa_sort::*;
+use rand::{RngExt, SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+/// Элемент: ключ и позиция во входе.
+type Item = (u32, usize);
+
+/// Сортировка с функцией сравнения. Функция сравнения `Send`, чтобы параллельные
+/// сортировки могли вызывать её из других потоков.
+type Sort = fn(&mut [Item], &mut (dyn FnMut(&Item, &Item) -> Ordering + Send));
+
+/// Сортировка в проверке.
+struct Algorithm {
+    name: &'static str,
+    sort: Sort,
+    /// Сортировка обещает стабильность.
+    stable: bool,
+    /// Сложность O(n²): проверяется только на коротких входах.
+    quadratic: bool,
+    /// Гарантированно O(n log n) сравнений, в том числе на враждебных входах.
+    /// Introsort до перехода на пирамидальную сортировку успевает сделать до `2 log n`
+    /// разбиений, поэтому граница в проверке — `5 n log n`.
+    n_log_n: bool,
+}
+
+const fn algorithm(name: &'static str, sort: Sort, stable: bool) -> Algorithm {
+    Algorithm {
+        name,
+        sort,
+        stable,
+        quadratic: false,
+        n_log_n: false,
+    }
+}
+
+const fn quadratic(name: &'static str, sort: Sort, stable: bool) -> Algorithm {
+    Algorithm {
+        quadratic: true,
+        ..algorithm(name, sort, stable)
+    }
+}
+
+const fn n_log_n(name: &'static str, sort: Sort, stable: bool) -> Algorithm {
+    Algorithm {
+        n_log_n: true,
+        ..algorithm(name, sort, stable)
+    }
+}
+
+/// Параллельные сортировки с маленьким порогом, чтобы потоки запускались и на коротких входах.
+const PARALLEL: ParallelConfig = ParallelConfig {
+    threads: 4,
+    cutoff: 64,
+};
+
+fn by_key(a: &Item, b: &Item) -> Ordering {
+    a.0.cmp(&b.0)
+}
+
+/// Сортирует `arr` по ключу и возвращает количество сравнений.
+fn count_comparisons(arr: &mut [Item], sort: Sort) -> usize {
+    let mut comparisons = 0;
+    sort(arr, &mut |a, b| {
+        comparisons += 1;
+        by_key(a, b)
+    });
+    comparisons
+}
+
+fn algorithms() -> Vec<Algorithm> {
+    vec![
+        quadratic("bubble", |arr, compare| bubble_sort_by(arr, compare), true),
+        quadratic(
+            "selection",
+            |arr, compare| selection_sort_by(arr, compare),
+            false,
+        ),
+        quadratic(
+            "insertion",
+            |arr, compare| insertion_sort_by(arr, compare),
+            true,
+        ),
+        quadratic(
+            "binary_insertion",
+            |arr, compare| binary_insertion_sort_by(arr, compare),
+            true,
+        ),
+        quadratic("gnome", |arr, compare| gnome_sort_by(arr, compare), true),
+        quadratic(
+            "cocktail_shaker",
+            |arr, compare| cocktail_shaker_sort_by(arr, compare),
+            true,
+        ),
+        quadratic("comb", |arr, compare| comb_sort_by(arr, compare), false),
+        // Опорный элемент — последний: на враждебных входах O(n²).
+        quadratic("quick", |arr, compare| quick_sort_by(arr, compare), false),
+        algorithm("shell", |arr, compare| shell_sort_by(arr, compare), false),
+        algorithm(
+            "three_way_quick",
+            |arr, compare| three_way_quick_sort_by(arr, compare),
+            false,
+        ),
+        algorithm(
+            "dual_pivot_quick",
+            |arr, compare| dual_pivot_quick_sort_by(arr, compare),
+            false,
+        ),
+        n_log_n("merge", |arr, compare| merge_sort_by(arr, compare), true),
+        n_log_n("heap", |arr, compare| heap_sort_by(arr, compare), false),
+        n_log_n("intro", |arr, compare| intro_sort_by(arr, compare), false),
+        n_log_n(
+            "partial",
+            |arr, compare| partial_sort_by(arr, arr.len(), compare),
+            false,
+        ),
+        n_log_n(
+            "merge_sorter_top_down",
+            |arr, compare| MergeSorter::new(MergeMode::TopDown).sort_by(arr, compare),
+            true,
+        ),
+        n_log_n(
+            "merge_sorter_bottom_up",
+            |arr, compare| MergeSorter::new(MergeMode::BottomUp).sort_by(arr, compare),
+            true,
+        ),
+        n_log_n(
+            "merge_sorter_natural",
+            |arr, compare| MergeSorter::new(MergeMode::Natural).sort_by(arr, compare),
+            true,
+        ),
+        n_log_n(
+            "parallel_merge",
+            |arr, compare| {
+                let compare = Mutex::new(compare);
+                PARALLEL.merge_sort_by(arr, |a, b| (compare.lock().unwrap())(a, b));
+            },
+            true,
+        ),
+        n_log_n(
+            "parallel_quick",
+            |arr, compare| {
+                let compare = Mutex::new(compare);
+                PARALLEL.quick_sort_by(arr, |a, b| (compare.lock().unwrap())(a, b));
+            },
+            false,
+        ),
+    ]
+}
+
+/// Сортировки по ключу без функции сравнения: переданная функция не вызывается,
+/// поэтому для них проверяются только порядок, перестановка и стабильность,
+/// без враждебных входов и подсчёта сравнений.
+fn key_sorts() -> Vec<Algorithm> {
+    vec![algorithm(
+        "counting_by_key",
+        |arr, _| counting_sort_by_key(arr, |item| item.0 as usize),
+        true,
+    )]
+}
+
+/// Генератор ключей заданной длины.
+type Generator = fn(&mut StdRng, usize) -> Vec<u32>;
+
+fn generators() -> Vec<(&'static str, Generator)> {
+    vec![
+        ("random", |rng, len| {
+            (0..len).map(|_| rng.random_range(0..1 << 20)).collect()
+        }),
+        ("few_unique", |rng, len| {
+            (0..len).map(|_| rng.random_range(0..4)).collect()
+        }),
+        ("sorted", |_, len| (0..len as u32).collect()),
+        ("reversed", |_, len| (0..len as u32).rev().collect()),
+        ("equal", |_, len| vec![7; len]),
+        ("organ_pipe", |_, len| {
+            (0..len).map(|i| i.min(len - 1 - i) as u32).collect()
+        }),
+        ("sawtooth", |rng, len| {
+            let period = rng.random_range(2..=16);
+            (0..len).map(|i| (i % period) as u32).collect()
+        }),
+        ("nearly_sorted", |rng, len| {
+            let mut keys: Vec<u32> = (0..len as u32).collect();
+            for _ in 0..len / 20 + 1 {
+                if len > 1 {
+                    keys.swap(rng.random_range(0..len), rng.random_range(0..len));
+                }
+            }
+            keys
+        }),
+        ("shuffled_permutation", |rng, len| {
+            let mut keys: Vec<u32> = (0..len as u32).collect();
+            keys.shuffle(rng);
+            keys
+        }),
+    ]
+}
+
+/// Зерно генератора: из `EXA_SORT_SEED` или случайное.
+fn seed() -> u64 {
+    env::var("EXA_SORT_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or_else(rand::random)
+}
+
+fn items(keys: &[u32]) -> Vec<Item> {
+    keys.iter().copied().zip(0..).collect()
+}
+
+/// Сортирует `input` и проверяет результат. Возвращает количество сравнений.
+fn check(algorithm: &Algorithm, input: &[Item], context: &str) -> usize {
+    let mut arr = input.to_vec();
+    let comparisons = count_comparisons(&mut arr, algorithm.sort);
+    let name = algorithm.name;
+
+    assert!(
+        arr.is_sorted_by_key(|item| item.0),
+        "{name}: не упорядочено, {context}"
+    );
+
+    let mut expected = input.to_vec();
+    expected.sort_by_key(|item| item.0);
+    if algorithm.stable {
+        assert_eq!(arr, expected, "{name}: нарушена стабильность, {context}");
+    } else {
+        let mut positions: Vec<usize> = arr.iter().map(|item| item.1).collect();
+        positions.sort_unstable();
+        assert!(
+            positions.iter().copied().eq(0..input.len()),
+            "{name}: не перестановка входа, {context}"
+        );
+        // Пары не распались: у каждого элемента ключ из его позиции во входе.
+        assert!(
+            arr.iter().all(|&(key, position)| input[position].0 == key),
+            "{name}: элемент потерял ключ, {context}"
+        );
+    }
+    comparisons
+}
+
+#[test]
+fn test_generated() {
+    let seed = seed();
+    let mut rng = StdRng::seed_from_u64(seed);
+    for algorithm in algorithms().into_iter().chain(key_sorts()) {
+        let max_len = if algorithm.quadratic { 300 } else { 3000 };
+        for (generator, generate) in generators() {
+            for len in [0, 1, 2, 3, 15, 16, 17, 100, max_len] {
+                let input = items(&generate(&mut rng, len));
+                let context = format!("{generator}, len = {len}, EXA_SORT_SEED={seed}");
+                check(&algorithm, &input, &context);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_random_lengths() {
+    let seed = seed();
+    let mut rng = StdRng::seed_from_u64(seed);
+    for algorithm in algorithms().into_iter().chain(key_sorts()) {
+        for _ in 0..50 {
+            let len = rng.random_range(0..200);
+            let keys: Vec<u32> = (0..len).map(|_| rng.random_range(0..50)).collect();
+            let context = format!("{keys:?}, EXA_SORT_SEED={seed}");
+            check(&algorithm, &items(&keys), &context);
+        }
+    }
+}
+
+/// Убийца быстрой сортировки Макилроя («A Killer Adversary for Quicksort»).
+///
+/// Сортирует позиции враждебной функцией сравнения: пока значения не определены
+/// («газ»), она отвечает так, чтобы опорный элемент оказался наименьшим, и фиксирует
+/// значения по мере надобности. Полученные значения — вход, на котором данная
+/// сортировка делает столько же сравнений, сколько при построении.
+fn mcilroy_killer(sort: Sort, len: usize) -> Vec<u32> {
+    let gas = len as u32;
+    let mut values = vec![gas; len];
+    let mut solid = 0;
+    let mut candidate = 0;
+
+    let mut positions: Vec<Item> = (0..len).map(|i| (0, i)).collect();
+    sort(&mut positions, &mut |a, b| {
+        let (x, y) = (a.1, b.1);
+        if values[x] == gas && values[y] == gas {
+            let frozen = if x == candidate { x } else { y };
+            values[frozen] = solid;
+            solid += 1;
+        }
+        if values[x] == gas {
+            candidate = x;
+        } else if values[y] == gas {
+            candidate = y;
+        }
+        values[x].cmp(&values[y])
+    });
+    values
+}
+
+#[test]
+fn test_mcilroy_killer() {
+    let len: usize = 2000;
+    let log_n = usize::BITS - len.leading_zeros();
+    let n_log_n = len * log_n as usize;
+    for algorithm in algorithms() {
+        let input = items(&mcilroy_killer(algorithm.sort, len));
+        let comparisons = check(&algorithm, &input, "mcilroy_killer");
+        if algorithm.n_log_n {
+            assert!(
+                comparisons <= 5 * n_log_n,
+                "{}: {comparisons} сравнений",
+                algorithm.name
+            );
+        }
+    }
+
+    // Без защиты от худшего случая быстрая сортировка становится квадратичной.
+    let quick = algorithms()
+        .into_iter()
+        .find(|algorithm| algorithm.name == "quick")
+        .expect("quick есть в списке");
+    let input = items(&mcilroy_killer(quick.sort, len));
+    assert!(check(&quick, &input, "mcilroy_killer") > len * len / 4);
+}
+
+#[test]
+fn test_nth_element_killer() {
+    let len = 20_000;
+    let select: Sort = |arr, compare| {
+        let k = arr.len() / 2;
+        nth_element_by(arr, k, compare);
+    };
+    let input = items(&mcilroy_killer(select, len));
+
+    let mut arr = input.clone();
+    let comparisons = count_comparisons(&mut arr, select);
+    let mut sorted = input;
+    sorted.sort_by_key(|item| item.0);
+    assert_eq!(arr[len / 2].0, sorted[len / 2].0);
+    assert!(comparisons < 40 * len, "{comparisons}");
+}
+
+#[test]
+fn test_non_comparison() {
+    let seed = seed();
+    let mut rng = StdRng::seed_from_u64(seed);
+    for (generator, generate) in generators() {
+        for len in [0, 1, 17, 1000] {
+            let keys = generate(&mut rng, len);
+            let mut expected = keys.clone();
+            expected.sort_unstable();
+            let context = format!("{generator}, len = {len}, EXA_SORT_SEED={seed}");
+
+            let mut arr = keys.clone();
+            counting_sort(&mut arr);
+            assert_eq!(arr, expected, "counting, {context}");
+
+            let mut arr = keys.clone();
+            radix_sort_lsd(&mut arr);
+            assert_eq!(arr, expected, "radix_sort_lsd, {context}");
+
+            let mut arr = keys.clone();
+            radix_sort_msd(&mut arr);
+            assert_eq!(arr, expected, "radix_sort_msd, {context}");
+
+            let mut arr: Vec<f64> = keys.iter().map(|&key| f64::from(key)).collect();
+            bucket_sort(&mut arr, 0.0..f64::from(1 << 20));
+            let expected: Vec<f64> = expected.iter().map(|&key| f64::from(key)).collect();
+            assert_eq!(arr, expected, "bucket, {context}");
+        }
+    }
+}